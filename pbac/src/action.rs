@@ -1,4 +1,6 @@
-use crate::ElementParseError;
+use crate::parser::{escape, Parser};
+use crate::{ElementParseError, Part};
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
 pub struct ScopedAction {
@@ -14,65 +16,43 @@ pub struct Action {
 
 impl ScopedAction {
     pub fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value.find(':') {
-            None => Err(ElementParseError {
-                token: value.to_string(),
-            }),
-            Some(index) => {
-                let scope = &value[0..index];
-
-                if scope.is_empty() {
-                    return Err(ElementParseError {
-                        token: scope.to_string(),
-                    });
-                }
-
-                let action = &value[index + 1..value.len()];
-
-                Ok(Self {
-                    scope: scope.to_string(),
-                    action: Action::parse(action)?,
-                })
-            }
-        }
+        let mut parser = Parser::new(value);
+
+        Ok(Self {
+            scope: parser.segment(Part::Scope)?.into(),
+            action: Action::read(&mut parser)?,
+        })
     }
 }
 
 impl Action {
-    pub fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value.find(':') {
-            None => Err(ElementParseError {
-                token: value.to_string(),
-            }),
-            Some(index) => {
-                let verb = &value[0..index];
-
-                if verb.is_empty() {
-                    return Err(ElementParseError {
-                        token: verb.to_string(),
-                    });
-                }
-
-                let resource = &value[index + 1..value.len()];
-
-                if resource.is_empty() {
-                    return Err(ElementParseError {
-                        token: resource.to_string(),
-                    });
-                }
-
-                Ok(Self {
-                    verb: verb.to_string(),
-                    resource: resource.to_string(),
-                })
-            }
-        }
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(Self {
+            verb: parser.segment(Part::Verb)?.into(),
+            resource: parser.last(Part::Resource)?.into(),
+        })
+    }
+}
+
+impl fmt::Display for ScopedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        escape(f, &self.scope)?;
+        write!(f, ":{}", self.action)
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        escape(f, &self.verb)?;
+        f.write_str(":")?;
+        escape(f, &self.resource)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ParseErrorKind;
 
     mod parse {
         use super::*;
@@ -95,7 +75,10 @@ mod tests {
         #[test]
         fn fail_scope_verb_empty() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 11,
+                part: Part::Resource,
+                input: "scope:verb:".to_string(),
             };
 
             let actual = ScopedAction::parse("scope:verb:").unwrap_err();
@@ -106,7 +89,10 @@ mod tests {
         #[test]
         fn fail_scope_empty_resource() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 6,
+                part: Part::Verb,
+                input: "scope::resource".to_string(),
             };
 
             let actual = ScopedAction::parse("scope::resource").unwrap_err();
@@ -117,7 +103,10 @@ mod tests {
         #[test]
         fn fail_scope_empty_empty() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 6,
+                part: Part::Verb,
+                input: "scope::".to_string(),
             };
 
             let actual = ScopedAction::parse("scope::").unwrap_err();
@@ -128,7 +117,10 @@ mod tests {
         #[test]
         fn fail_scope_token() {
             let expected = ElementParseError {
-                token: "token".to_string(),
+                kind: ParseErrorKind::MissingSeparator,
+                offset: 11,
+                part: Part::Verb,
+                input: "scope:token".to_string(),
             };
 
             let actual = ScopedAction::parse("scope:token").unwrap_err();
@@ -139,7 +131,10 @@ mod tests {
         #[test]
         fn fail_empty_verb_resource() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: ":verb:resource".to_string(),
             };
 
             let actual = ScopedAction::parse(":verb:resource").unwrap_err();
@@ -150,7 +145,10 @@ mod tests {
         #[test]
         fn fail_empty_verb_empty() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: ":verb:".to_string(),
             };
 
             let actual = ScopedAction::parse(":verb:").unwrap_err();
@@ -161,7 +159,10 @@ mod tests {
         #[test]
         fn fail_empty_empty_resource() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: "::resource".to_string(),
             };
 
             let actual = ScopedAction::parse("::resource").unwrap_err();
@@ -172,7 +173,10 @@ mod tests {
         #[test]
         fn fail_empty_empty_empty() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: "::".to_string(),
             };
 
             let actual = ScopedAction::parse("::").unwrap_err();
@@ -183,12 +187,29 @@ mod tests {
         #[test]
         fn fail_empty_token() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: ":token".to_string(),
             };
 
             let actual = ScopedAction::parse(":token").unwrap_err();
 
             assert_eq!(actual, expected);
         }
+
+        #[test]
+        fn fail_invalid_character() {
+            let expected = ElementParseError {
+                kind: ParseErrorKind::InvalidCharacter,
+                offset: 8,
+                part: Part::Verb,
+                input: "scope:ve\trb:resource".to_string(),
+            };
+
+            let actual = ScopedAction::parse("scope:ve\trb:resource").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
     }
 }
@@ -0,0 +1,180 @@
+use crate::analysis::{partition, Example, Region};
+use crate::{Effect, Policy};
+
+/// The PolicyDiff describes the access added and removed by changing one policy set into another.
+#[derive(Debug, PartialEq)]
+pub struct PolicyDiff {
+    /// Regions that were denied before and are allowed after.
+    pub allowed: Vec<Change>,
+
+    /// Regions that were allowed before and are denied after.
+    pub denied: Vec<Change>,
+}
+
+/// The Change describes a region whose effect differs between two policy sets.
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub region: Region,
+    pub example: Example,
+}
+
+/// Compares two policy sets symbolically over the patterns of their documents.
+///
+/// Requests are taken to carry a single resource, and the principals of the policies are ignored,
+/// so the policy sets should be those that apply to one principal.
+///
+/// The regions hold untyped resources. A typed resource within a region changes as the region
+/// does when it fits the action, having the scope of the action and the type of its resource, and
+/// is denied before and after otherwise.
+pub fn diff(before: &[Policy], after: &[Policy]) -> PolicyDiff {
+    let mut policy_diff = PolicyDiff {
+        allowed: vec![],
+        denied: vec![],
+    };
+
+    for cell in partition(&[before, after]) {
+        let changes = match (cell.effects[0], cell.effects[1]) {
+            (Effect::Deny, Effect::Allow) => &mut policy_diff.allowed,
+            (Effect::Allow, Effect::Deny) => &mut policy_diff.denied,
+            _ => continue,
+        };
+
+        changes.push(Change {
+            example: cell.region.example(),
+            region: cell.region,
+        });
+    }
+
+    policy_diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{Segment, Segments};
+    use crate::is_authorized;
+    use crate::test_utils::policy;
+
+    fn effect(policies: &[Policy], example: &Example) -> Effect {
+        is_authorized(
+            policies,
+            &example.action,
+            std::slice::from_ref(&example.resource),
+        )
+        .0
+    }
+
+    #[test]
+    fn pass_unchanged() {
+        let before = vec![policy(Effect::Allow, "scope:verb:*", "scope:*")];
+        let after = vec![policy(Effect::Allow, "scope:verb:*", "scope:*")];
+
+        let actual = diff(&before, &after);
+
+        assert_eq!(actual.allowed, vec![]);
+        assert_eq!(actual.denied, vec![]);
+    }
+
+    #[test]
+    fn pass_allowed() {
+        let before = vec![];
+        let after = vec![policy(Effect::Allow, "scope:verb:resource", "*")];
+
+        let actual = diff(&before, &after);
+
+        assert_eq!(actual.allowed.len(), 1);
+        assert_eq!(actual.denied, vec![]);
+
+        let change = &actual.allowed[0];
+        assert_eq!(change.region.scope, Segment::Value("scope".to_string()));
+        assert_eq!(change.region.verb, Segment::Value("verb".to_string()));
+        assert_eq!(change.region.segments, Segments::ExceptCounts(vec![]));
+        assert_eq!(effect(&before, &change.example), Effect::Deny);
+        assert_eq!(effect(&after, &change.example), Effect::Allow);
+    }
+
+    #[test]
+    fn pass_denied() {
+        let before = vec![policy(Effect::Allow, "scope:*", "scope:*")];
+        let after = vec![
+            policy(Effect::Allow, "scope:*", "scope:*"),
+            policy(Effect::Deny, "scope:delete:*", "scope:*"),
+        ];
+
+        let actual = diff(&before, &after);
+
+        assert_eq!(actual.allowed, vec![]);
+        assert_eq!(actual.denied.len(), 1);
+
+        let change = &actual.denied[0];
+        assert_eq!(change.region.verb, Segment::Value("delete".to_string()));
+        assert_eq!(effect(&before, &change.example), Effect::Allow);
+        assert_eq!(effect(&after, &change.example), Effect::Deny);
+    }
+
+    #[test]
+    fn pass_typed() {
+        let before = vec![];
+        let after = vec![policy(Effect::Allow, "docs:read:document", "docs:*")];
+
+        let actual = diff(&before, &after);
+
+        assert_eq!(actual.allowed.len(), 1);
+
+        let example = &actual.allowed[0].example;
+        let effect = |policies: &[Policy], resource_type: &str| {
+            let resources = vec![example.resource.clone().with_type(resource_type)];
+
+            is_authorized(policies, &example.action, &resources).0
+        };
+        assert_eq!(effect(&before, "document"), Effect::Deny);
+        assert_eq!(effect(&after, "document"), Effect::Allow);
+        assert_eq!(effect(&before, "folder"), Effect::Deny);
+        assert_eq!(effect(&after, "folder"), Effect::Deny);
+    }
+
+    #[test]
+    fn pass_narrowed() {
+        let before = vec![policy(Effect::Allow, "*", "scope:*")];
+        let after = vec![policy(Effect::Allow, "*", "scope:resource")];
+
+        let actual = diff(&before, &after);
+
+        assert_eq!(actual.allowed, vec![]);
+        assert_eq!(actual.denied.len(), 2);
+        assert_eq!(
+            actual.denied[0].region.segments,
+            Segments::Exactly(vec![Segment::Except(vec!["resource".to_string()])])
+        );
+        assert_eq!(
+            actual.denied[1].region.segments,
+            Segments::ExceptCounts(vec![1])
+        );
+
+        for change in &actual.denied {
+            assert_eq!(effect(&before, &change.example), Effect::Allow);
+            assert_eq!(effect(&after, &change.example), Effect::Deny);
+        }
+    }
+
+    #[test]
+    fn pass_independent_parts() {
+        let mut before = vec![];
+        for index in 0..16 {
+            before.push(policy(Effect::Allow, &format!("a{}:*", index), "*"));
+            before.push(policy(Effect::Allow, &format!("*:v{}:*", index), "*"));
+            before.push(policy(Effect::Allow, &format!("*:*:t{}", index), "*"));
+            before.push(policy(Effect::Allow, "*", &format!("s{}:*", index)));
+            before.push(policy(Effect::Allow, "*", &format!("*:r{}", index)));
+        }
+
+        let actual = diff(&before, &[]);
+
+        assert_eq!(actual.allowed, vec![]);
+        assert_eq!(actual.denied.len(), before.len());
+
+        for change in &actual.denied {
+            assert_eq!(effect(&before, &change.example), Effect::Allow);
+        }
+    }
+}
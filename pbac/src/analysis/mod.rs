@@ -0,0 +1,441 @@
+mod diff;
+mod subsumption;
+
+use crate::action::Action;
+use crate::{Effect, Policy, ScopedAction, ScopedResource};
+pub use diff::{diff, Change, PolicyDiff};
+pub use subsumption::{check_equivalent, check_subset, Counterexample, Verdict};
+
+/// The number of single valued request parts a region constrains: scope, verb, action resource
+/// and resource scope.
+const DIMENSIONS: usize = 4;
+
+/// The Segment describes the values one part of a request may take within a region.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segment {
+    /// Exactly this value.
+    Value(String),
+
+    /// Any value except these, which are covered by other regions.
+    Except(Vec<String>),
+}
+
+/// The Segments describes the segments a resource may have within a region.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Segments {
+    /// Exactly as many segments as described, each taking the values described.
+    Exactly(Vec<Segment>),
+
+    /// Any number of segments other than these counts, each taking any value.
+    ExceptCounts(Vec<usize>),
+}
+
+/// The Region describes a set of requests that every policy treats in the same way.
+#[derive(Debug, PartialEq)]
+pub struct Region {
+    /// The scope of the action.
+    pub scope: Segment,
+
+    /// The verb of the action.
+    pub verb: Segment,
+
+    /// The resource of the action.
+    pub action_resource: Segment,
+
+    /// The scope of the resource.
+    pub resource_scope: Segment,
+
+    /// The segments of the resource.
+    pub segments: Segments,
+}
+
+/// The Example describes a concrete request within a region.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub action: ScopedAction,
+    pub resource: ScopedResource,
+}
+
+impl Segment {
+    fn example(&self) -> String {
+        match self {
+            Segment::Value(value) => value.clone(),
+            Segment::Except(values) => (0..)
+                .map(|index| match index {
+                    0 => "x".to_string(),
+                    index => format!("x{}", index),
+                })
+                .find(|candidate| !values.contains(candidate))
+                .unwrap(),
+        }
+    }
+}
+
+impl Segments {
+    fn example(&self) -> Vec<String> {
+        match self {
+            Segments::Exactly(segments) => segments.iter().map(Segment::example).collect(),
+            Segments::ExceptCounts(counts) => {
+                let count = (1..).find(|count| !counts.contains(count)).unwrap();
+
+                vec!["x".to_string(); count]
+            }
+        }
+    }
+}
+
+impl Region {
+    fn new(parts: &[Segment], segments: Segments) -> Self {
+        Self {
+            scope: parts[0].clone(),
+            verb: parts[1].clone(),
+            action_resource: parts[2].clone(),
+            resource_scope: parts[3].clone(),
+            segments,
+        }
+    }
+
+    /// Returns a concrete request that falls within the region.
+    pub fn example(&self) -> Example {
+        Example {
+            action: ScopedAction {
+                scope: self.scope.example(),
+                action: Action {
+                    verb: self.verb.example(),
+                    resource: self.action_resource.example(),
+                },
+            },
+            resource: ScopedResource {
+                scope: self.resource_scope.example(),
+                segments: self.segments.example(),
+                resource_type: None,
+            },
+        }
+    }
+}
+
+/// A region together with the effect each policy set has on it.
+pub(crate) struct Cell {
+    pub(crate) region: Region,
+    pub(crate) effects: Vec<Effect>,
+}
+
+/// One action document paired with one resource document of a policy.
+struct Statement<'a> {
+    set: usize,
+    effect: Effect,
+    pattern: [Option<&'a str>; DIMENSIONS],
+    segments: Option<Vec<Option<&'a str>>>,
+}
+
+/// Partitions the request space into regions on which every policy set has a constant effect.
+///
+/// Every part of a request is split into the literal values mentioned by the policies plus the
+/// remaining values, which no policy can tell apart. Resources are first split on the number of
+/// segments the policies mention, then segment by segment. A region is not split any further once
+/// the effect of every policy set is the same throughout it, so the parts it leaves unconstrained
+/// take any value. A request is assumed to carry one resource.
+///
+/// Resources are partitioned untyped. A typed resource that fits the action is decided as its
+/// untyped form, and one that does not is denied by every policy set.
+pub(crate) fn partition(sets: &[&[Policy]]) -> Vec<Cell> {
+    let statements = sets
+        .iter()
+        .enumerate()
+        .flat_map(|(set, policies)| statements(set, policies))
+        .collect::<Vec<_>>();
+
+    let mut cells = vec![];
+    split(
+        &statements.iter().collect::<Vec<_>>(),
+        &mut vec![],
+        sets.len(),
+        &mut cells,
+    );
+    cells
+}
+
+fn statements(set: usize, policies: &[Policy]) -> impl Iterator<Item = Statement<'_>> {
+    policies.iter().flat_map(move |policy| {
+        policy.actions.iter().flat_map(move |action| {
+            policy.resources.iter().map(move |resource| {
+                let [scope, verb, action_resource] = action.pattern();
+                let (resource_scope, segments) = resource.pattern();
+
+                Statement {
+                    set,
+                    effect: policy.effect,
+                    pattern: [scope, verb, action_resource, resource_scope],
+                    segments,
+                }
+            })
+        })
+    })
+}
+
+fn split(statements: &[&Statement], parts: &mut Vec<Segment>, sets: usize, cells: &mut Vec<Cell>) {
+    let dimension = parts.len();
+
+    if dimension == DIMENSIONS {
+        split_counts(statements, parts, sets, cells);
+        return;
+    }
+
+    let settled = settle(statements, sets, |statement| {
+        statement.pattern[dimension..].iter().all(Option::is_none) && statement.segments.is_none()
+    });
+
+    if let Some(effects) = settled {
+        let mut parts = parts.clone();
+        parts.resize(DIMENSIONS, Segment::Except(vec![]));
+
+        cells.push(Cell {
+            region: Region::new(&parts, Segments::ExceptCounts(vec![])),
+            effects,
+        });
+        return;
+    }
+
+    split_values(
+        statements,
+        |statement| statement.pattern[dimension],
+        |segment, matches| {
+            parts.push(segment);
+            split(&matches, parts, sets, cells);
+            parts.pop();
+        },
+    );
+}
+
+fn split_counts(statements: &[&Statement], parts: &[Segment], sets: usize, cells: &mut Vec<Cell>) {
+    if let Some(effects) = settle(statements, sets, |statement| statement.segments.is_none()) {
+        cells.push(Cell {
+            region: Region::new(parts, Segments::ExceptCounts(vec![])),
+            effects,
+        });
+        return;
+    }
+
+    let mut counts = statements
+        .iter()
+        .filter_map(|statement| statement.segments.as_ref().map(Vec::len))
+        .collect::<Vec<_>>();
+    counts.sort_unstable();
+    counts.dedup();
+
+    for &count in &counts {
+        let matches = statements
+            .iter()
+            .filter(|statement| {
+                statement
+                    .segments
+                    .as_ref()
+                    .is_none_or(|segments| segments.len() == count)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        split_segments(&matches, parts, count, &mut vec![], sets, cells);
+    }
+
+    let matches = statements
+        .iter()
+        .filter(|statement| statement.segments.is_none())
+        .copied()
+        .collect::<Vec<_>>();
+
+    cells.push(Cell {
+        region: Region::new(parts, Segments::ExceptCounts(counts)),
+        effects: decide(&matches, sets),
+    });
+}
+
+fn split_segments(
+    statements: &[&Statement],
+    parts: &[Segment],
+    count: usize,
+    segments: &mut Vec<Segment>,
+    sets: usize,
+    cells: &mut Vec<Cell>,
+) {
+    let index = segments.len();
+
+    let settled = settle(statements, sets, |statement| {
+        statement
+            .segments
+            .as_ref()
+            .is_none_or(|segments| segments[index..].iter().all(Option::is_none))
+    });
+
+    if let Some(effects) = settled {
+        let mut segments = segments.clone();
+        segments.resize(count, Segment::Except(vec![]));
+
+        cells.push(Cell {
+            region: Region::new(parts, Segments::Exactly(segments)),
+            effects,
+        });
+        return;
+    }
+
+    split_values(
+        statements,
+        |statement| {
+            statement
+                .segments
+                .as_ref()
+                .and_then(|segments| segments[index])
+        },
+        |segment, matches| {
+            segments.push(segment);
+            split_segments(&matches, parts, count, segments, sets, cells);
+            segments.pop();
+        },
+    );
+}
+
+/// Splits the statements on each literal value of a part, and on every other value.
+fn split_values<'s, 'a>(
+    statements: &[&'s Statement<'a>],
+    pattern: impl Fn(&Statement<'a>) -> Option<&'a str>,
+    mut each: impl FnMut(Segment, Vec<&'s Statement<'a>>),
+) {
+    let mut values = statements
+        .iter()
+        .filter_map(|statement| pattern(statement))
+        .collect::<Vec<_>>();
+    values.sort_unstable();
+    values.dedup();
+
+    for &value in &values {
+        let matches = statements
+            .iter()
+            .filter(|statement| pattern(statement).is_none_or(|v| v == value))
+            .copied()
+            .collect::<Vec<_>>();
+
+        each(Segment::Value(value.to_string()), matches);
+    }
+
+    let matches = statements
+        .iter()
+        .filter(|statement| pattern(statement).is_none())
+        .copied()
+        .collect::<Vec<_>>();
+
+    each(
+        Segment::Except(values.iter().map(|value| value.to_string()).collect()),
+        matches,
+    );
+}
+
+/// Applies deny-overrides to the statements of each policy set, every one of which matches the
+/// whole region.
+fn decide(statements: &[&Statement], sets: usize) -> Vec<Effect> {
+    settle(statements, sets, |_| true).unwrap()
+}
+
+/// Returns the effect of each policy set if it is the same on every request of the branch, so that
+/// the branch need not be split any further.
+///
+/// A statement `covers` the branch when it matches every request of it. The effect of a set is
+/// settled when a covering statement denies, when a covering statement allows and none denies, or
+/// when the set has no statement.
+fn settle(
+    statements: &[&Statement],
+    sets: usize,
+    covers: impl Fn(&Statement) -> bool,
+) -> Option<Vec<Effect>> {
+    (0..sets)
+        .map(|set| {
+            let statements = statements
+                .iter()
+                .filter(|statement| statement.set == set)
+                .collect::<Vec<_>>();
+
+            let denies = statements
+                .iter()
+                .filter(|statement| statement.effect == Effect::Deny)
+                .collect::<Vec<_>>();
+
+            if statements.is_empty() || denies.iter().any(|statement| covers(statement)) {
+                Some(Effect::Deny)
+            } else if denies.is_empty() && statements.iter().any(|statement| covers(statement)) {
+                Some(Effect::Allow)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod example {
+        use super::*;
+
+        #[test]
+        fn pass_value() {
+            let expected = "value".to_string();
+
+            let actual = Segment::Value("value".to_string()).example();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_except() {
+            let expected = "x2".to_string();
+
+            let actual = Segment::Except(vec!["x".to_string(), "x1".to_string()]).example();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_except_counts() {
+            let expected = vec!["x".to_string(), "x".to_string()];
+
+            let actual = Segments::ExceptCounts(vec![1, 3]).example();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod partition {
+        use super::*;
+        use crate::{ActionDocument, Element, ResourceDocument};
+
+        #[test]
+        fn pass_segments() {
+            let policies = vec![Policy {
+                actions: vec![ActionDocument::parse("*").unwrap()],
+                effect: Effect::Allow,
+                principals: vec![],
+                resources: vec![ResourceDocument::parse("scope:*:account:*").unwrap()],
+            }];
+
+            let allowed = partition(&[&policies])
+                .into_iter()
+                .filter(|cell| cell.effects[0] == Effect::Allow)
+                .map(|cell| cell.region)
+                .collect::<Vec<_>>();
+
+            assert_eq!(allowed.len(), 1);
+            assert_eq!(
+                allowed[0].segments,
+                Segments::Exactly(vec![
+                    Segment::Except(vec![]),
+                    Segment::Value("account".to_string()),
+                    Segment::Except(vec![]),
+                ])
+            );
+            assert_eq!(
+                allowed[0].example().resource.to_string(),
+                "scope:x:account:x"
+            );
+        }
+    }
+}
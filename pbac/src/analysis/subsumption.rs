@@ -0,0 +1,192 @@
+use crate::analysis::{partition, Example};
+use crate::{Effect, Policy};
+
+/// The Counterexample describes a concrete request on which two policy sets disagree.
+#[derive(Debug, PartialEq)]
+pub struct Counterexample {
+    pub example: Example,
+
+    /// The effect of the left policy set on the request.
+    pub left: Effect,
+
+    /// The effect of the right policy set on the request.
+    pub right: Effect,
+}
+
+/// The Verdict describes the outcome of comparing two policy sets.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Holds,
+    Counterexample(Counterexample),
+}
+
+/// Checks that `left` grants a subset of what `right` grants.
+///
+/// The check holds for requests that carry a single resource. A request for several resources is
+/// allowed when any policy applies to any of them, so `left` may allow such a request that `right`
+/// denies through a deny on another of its resources. The principals of the policies are ignored,
+/// so both sets should be those that apply to one principal.
+pub fn check_subset(left: &[Policy], right: &[Policy]) -> Verdict {
+    find(left, right, |left, right| {
+        left == Effect::Allow && right == Effect::Deny
+    })
+}
+
+/// Checks that `left` and `right` grant exactly the same requests that carry a single resource,
+/// ignoring the principals of the policies as `check_subset` does.
+pub fn check_equivalent(left: &[Policy], right: &[Policy]) -> Verdict {
+    find(left, right, |left, right| left != right)
+}
+
+fn find(left: &[Policy], right: &[Policy], predicate: impl Fn(Effect, Effect) -> bool) -> Verdict {
+    match partition(&[left, right])
+        .into_iter()
+        .find(|cell| predicate(cell.effects[0], cell.effects[1]))
+    {
+        None => Verdict::Holds,
+        Some(cell) => Verdict::Counterexample(Counterexample {
+            example: cell.region.example(),
+            left: cell.effects[0],
+            right: cell.effects[1],
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+    use crate::{is_authorized, ActionDocument, Element};
+
+    fn effect(policies: &[Policy], example: &Example) -> Effect {
+        is_authorized(
+            policies,
+            &example.action,
+            std::slice::from_ref(&example.resource),
+        )
+        .0
+    }
+
+    fn counterexample(verdict: Verdict) -> Counterexample {
+        match verdict {
+            Verdict::Holds => panic!("expected a counterexample"),
+            Verdict::Counterexample(counterexample) => counterexample,
+        }
+    }
+
+    mod check_subset {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let left = vec![policy(Effect::Allow, "scope:verb:*", "scope:resource")];
+            let right = vec![policy(Effect::Allow, "scope:*", "scope:*")];
+
+            let actual = check_subset(&left, &right);
+
+            assert_eq!(actual, Verdict::Holds);
+        }
+
+        #[test]
+        fn pass_empty() {
+            let left = vec![];
+            let right = vec![policy(Effect::Allow, "scope:*", "scope:*")];
+
+            let actual = check_subset(&left, &right);
+
+            assert_eq!(actual, Verdict::Holds);
+        }
+
+        #[test]
+        fn fail() {
+            let left = vec![policy(Effect::Allow, "scope:*", "scope:*")];
+            let right = vec![policy(Effect::Allow, "scope:verb:*", "scope:*")];
+
+            let actual = counterexample(check_subset(&left, &right));
+
+            assert_eq!(actual.left, Effect::Allow);
+            assert_eq!(actual.right, Effect::Deny);
+            assert_eq!(effect(&left, &actual.example), Effect::Allow);
+            assert_eq!(effect(&right, &actual.example), Effect::Deny);
+        }
+
+        #[test]
+        fn fail_deny_removed() {
+            let left = vec![policy(Effect::Allow, "*", "*")];
+            let right = vec![
+                policy(Effect::Allow, "*", "*"),
+                policy(Effect::Deny, "scope:delete:*", "*"),
+            ];
+
+            let actual = counterexample(check_subset(&left, &right));
+
+            assert_eq!(actual.example.action.scope, "scope");
+            assert_eq!(actual.example.action.action.verb, "delete");
+        }
+    }
+
+    mod check_equivalent {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let left = vec![Policy {
+                actions: vec![
+                    ActionDocument::parse("scope:read:*").unwrap(),
+                    ActionDocument::parse("scope:write:*").unwrap(),
+                ],
+                ..policy(Effect::Allow, "scope:read:*", "scope:*")
+            }];
+            let right = vec![
+                policy(Effect::Allow, "scope:write:*", "scope:*"),
+                policy(Effect::Allow, "scope:read:*", "scope:*"),
+            ];
+
+            let actual = check_equivalent(&left, &right);
+
+            assert_eq!(actual, Verdict::Holds);
+        }
+
+        #[test]
+        fn pass_redundant_deny() {
+            let left = vec![policy(Effect::Allow, "scope:read:*", "*")];
+            let right = vec![
+                policy(Effect::Allow, "scope:read:*", "*"),
+                policy(Effect::Deny, "scope:write:*", "*"),
+            ];
+
+            let actual = check_equivalent(&left, &right);
+
+            assert_eq!(actual, Verdict::Holds);
+        }
+
+        #[test]
+        fn fail_wider() {
+            let left = vec![policy(Effect::Allow, "scope:read:*", "*")];
+            let right = vec![
+                policy(Effect::Allow, "scope:*", "*"),
+                policy(Effect::Deny, "scope:write:*", "*"),
+            ];
+
+            let actual = counterexample(check_equivalent(&left, &right));
+
+            assert_eq!(actual.left, Effect::Deny);
+            assert_eq!(actual.right, Effect::Allow);
+            assert_eq!(effect(&left, &actual.example), Effect::Deny);
+            assert_eq!(effect(&right, &actual.example), Effect::Allow);
+        }
+
+        #[test]
+        fn fail() {
+            let left = vec![policy(Effect::Allow, "scope:read:*", "*")];
+            let right = vec![policy(Effect::Allow, "scope:read:*", "scope:*")];
+
+            let actual = counterexample(check_equivalent(&left, &right));
+
+            assert_eq!(actual.left, Effect::Allow);
+            assert_eq!(actual.right, Effect::Deny);
+            assert_eq!(effect(&left, &actual.example), Effect::Allow);
+            assert_eq!(effect(&right, &actual.example), Effect::Deny);
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use crate::action::Action;
+use crate::authorizer::{combine, is_applicable, is_principal, is_typed};
+use crate::{
+    is_authorized, Decision, DenyOverrides, Effect, Policy, Principal, ScopedAction, ScopedResource,
+};
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+/// The TrustPolicy describes which principals may assume a role.
+///
+/// Its policies are evaluated against the `sts:assume:role` action on the role's resource, and
+/// only apply to the principals they name. A typed role resource must therefore be of scope `sts`
+/// and type `role`.
+#[derive(Debug, Default, PartialEq)]
+pub struct TrustPolicy {
+    pub policies: Vec<Policy>,
+}
+
+/// The AssumableRole describes a role that principals trusted by it can assume, taking on its
+/// permissions.
+#[derive(Debug, PartialEq)]
+pub struct AssumableRole {
+    /// The resource identifying the role, such as `sts:role/deployer`.
+    pub resource: ScopedResource,
+
+    pub trust_policy: TrustPolicy,
+
+    /// The permissions of the role.
+    pub policies: Vec<Policy>,
+}
+
+/// The AssumedRole describes a principal acting as a role until it expires.
+#[derive(Debug, PartialEq)]
+pub struct AssumedRole<'a> {
+    /// The principal of the role, identified by its resource.
+    pub principal: Principal,
+
+    /// The role whose permissions the principal carries.
+    pub role: &'a AssumableRole,
+
+    /// The principal that assumed the role, for auditing.
+    pub caller: Principal,
+
+    pub expires_at: SystemTime,
+}
+
+/// The AssumeRoleError describes why a role could not be assumed.
+#[derive(Debug, PartialEq)]
+pub enum AssumeRoleError {
+    /// The trust policy of the role does not allow the caller to assume it.
+    Denied { role: String, caller: Principal },
+
+    /// The role would expire later than the system can represent.
+    InvalidDuration { role: String, duration: Duration },
+
+    /// The resource of the role is typed, but does not fit the `sts:assume:role` action, so no
+    /// trust policy could allow assuming it.
+    InvalidResource { role: String },
+}
+
+impl AssumableRole {
+    /// Returns the role, if its resource fits the `sts:assume:role` action its trust policy is
+    /// evaluated against.
+    pub fn new(
+        resource: ScopedResource,
+        trust_policy: TrustPolicy,
+        policies: Vec<Policy>,
+    ) -> Result<Self, AssumeRoleError> {
+        let role = Self {
+            resource,
+            trust_policy,
+            policies,
+        };
+
+        role.check_resource()?;
+
+        Ok(role)
+    }
+
+    /// Assumes the role as the caller until `duration` after `now`, if its trust policy allows it
+    /// and that time can be represented.
+    pub fn assume(
+        &self,
+        caller: &Principal,
+        now: SystemTime,
+        duration: Duration,
+    ) -> Result<AssumedRole<'_>, AssumeRoleError> {
+        self.check_resource()?;
+
+        let action = assume_action();
+        let resources = std::slice::from_ref(&self.resource);
+
+        let (decision, _) = combine(&DenyOverrides, &self.trust_policy.policies, |policy| {
+            !policy.principals.is_empty()
+                && is_principal(policy, std::slice::from_ref(caller))
+                && is_applicable(policy, &action, resources)
+        });
+
+        if decision != Decision::Allow {
+            return Err(AssumeRoleError::Denied {
+                role: self.resource.to_string(),
+                caller: caller.clone(),
+            });
+        }
+
+        let expires_at =
+            now.checked_add(duration)
+                .ok_or_else(|| AssumeRoleError::InvalidDuration {
+                    role: self.resource.to_string(),
+                    duration,
+                })?;
+
+        Ok(AssumedRole {
+            principal: Principal(self.resource.to_string()),
+            role: self,
+            caller: caller.clone(),
+            expires_at,
+        })
+    }
+
+    fn check_resource(&self) -> Result<(), AssumeRoleError> {
+        if !is_typed(&assume_action(), std::slice::from_ref(&self.resource)) {
+            return Err(AssumeRoleError::InvalidResource {
+                role: self.resource.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the `sts:assume:role` action trust policies are evaluated against.
+fn assume_action() -> ScopedAction {
+    ScopedAction {
+        scope: "sts".to_string(),
+        action: Action {
+            verb: "assume".to_string(),
+            resource: "role".to_string(),
+        },
+    }
+}
+
+impl AssumedRole<'_> {
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.expires_at
+    }
+
+    /// Evaluates the permissions of the role against the action and resources, denying once the
+    /// role has expired.
+    pub fn is_authorized(
+        &self,
+        now: SystemTime,
+        scoped_action: &ScopedAction,
+        scoped_resources: &[ScopedResource],
+    ) -> (Effect, Vec<&Policy>) {
+        if self.is_expired(now) {
+            return (Effect::Deny, vec![]);
+        }
+
+        is_authorized(&self.role.policies, scoped_action, scoped_resources)
+    }
+}
+
+impl fmt::Display for AssumeRoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssumeRoleError::Denied { role, caller } => {
+                write!(f, "{} may not assume {}", caller.0, role)
+            }
+            AssumeRoleError::InvalidDuration { role, duration } => {
+                write!(f, "{} cannot be assumed for {:?}", role, duration)
+            }
+            AssumeRoleError::InvalidResource { role } => {
+                write!(f, "{} is not of scope sts and type role", role)
+            }
+        }
+    }
+}
+
+impl Error for AssumeRoleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{principal, principal_policy};
+
+    fn deployer(trust_policy: Vec<Policy>) -> AssumableRole {
+        AssumableRole {
+            resource: ScopedResource::parse("sts:role/deployer").unwrap(),
+            trust_policy: TrustPolicy {
+                policies: trust_policy,
+            },
+            policies: vec![principal_policy(Effect::Allow, &[], "deploy:*", "*")],
+        }
+    }
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    mod new {
+        use super::*;
+
+        #[test]
+        fn pass_untyped() {
+            let actual = AssumableRole::new(
+                ScopedResource::parse("iam:role/deployer").unwrap(),
+                TrustPolicy::default(),
+                vec![],
+            );
+
+            assert!(actual.is_ok());
+        }
+
+        #[test]
+        fn pass_typed() {
+            let actual = AssumableRole::new(
+                ScopedResource::parse("sts:deployer")
+                    .unwrap()
+                    .with_type("role"),
+                TrustPolicy::default(),
+                vec![],
+            );
+
+            assert!(actual.is_ok());
+        }
+
+        #[test]
+        fn fail_typed_scope() {
+            let expected = AssumeRoleError::InvalidResource {
+                role: "iam:deployer".to_string(),
+            };
+
+            let actual = AssumableRole::new(
+                ScopedResource::parse("iam:deployer")
+                    .unwrap()
+                    .with_type("role"),
+                TrustPolicy::default(),
+                vec![],
+            )
+            .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod assume {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let role = deployer(vec![principal_policy(
+                Effect::Allow,
+                &["user:alice"],
+                "sts:assume:role",
+                "sts:role/deployer",
+            )]);
+
+            let actual = role
+                .assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR)
+                .unwrap();
+
+            assert_eq!(
+                actual,
+                AssumedRole {
+                    principal: principal("sts:role/deployer"),
+                    role: &role,
+                    caller: principal("user:alice"),
+                    expires_at: SystemTime::UNIX_EPOCH + HOUR,
+                }
+            );
+        }
+
+        #[test]
+        fn pass_other_scope() {
+            let role = AssumableRole {
+                resource: ScopedResource::parse("iam:role/deployer").unwrap(),
+                ..deployer(vec![principal_policy(
+                    Effect::Allow,
+                    &["user:alice"],
+                    "sts:assume:role",
+                    "iam:role/deployer",
+                )])
+            };
+
+            let actual = role.assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR);
+
+            assert!(actual.is_ok());
+        }
+
+        #[test]
+        fn fail_typed_scope() {
+            let expected = AssumeRoleError::InvalidResource {
+                role: "iam:deployer".to_string(),
+            };
+
+            let role = AssumableRole {
+                resource: ScopedResource::parse("iam:deployer")
+                    .unwrap()
+                    .with_type("role"),
+                ..deployer(vec![principal_policy(Effect::Allow, &["*"], "*", "*")])
+            };
+
+            let actual = role
+                .assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR)
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_untrusted() {
+            let expected = AssumeRoleError::Denied {
+                role: "sts:role/deployer".to_string(),
+                caller: principal("user:bob"),
+            };
+
+            let role = deployer(vec![principal_policy(
+                Effect::Allow,
+                &["user:alice"],
+                "sts:assume:role",
+                "*",
+            )]);
+
+            let actual = role
+                .assume(&principal("user:bob"), SystemTime::UNIX_EPOCH, HOUR)
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_duration() {
+            let expected = AssumeRoleError::InvalidDuration {
+                role: "sts:role/deployer".to_string(),
+                duration: Duration::MAX,
+            };
+
+            let role = deployer(vec![principal_policy(
+                Effect::Allow,
+                &["user:alice"],
+                "sts:assume:role",
+                "*",
+            )]);
+
+            let actual = role
+                .assume(&principal("user:alice"), SystemTime::now(), Duration::MAX)
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_without_principals() {
+            let role = deployer(vec![principal_policy(Effect::Allow, &[], "*", "*")]);
+
+            let actual = role.assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR);
+
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn fail_other_role() {
+            let role = deployer(vec![principal_policy(
+                Effect::Allow,
+                &["*"],
+                "sts:assume:role",
+                "sts:role/admin",
+            )]);
+
+            let actual = role.assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR);
+
+            assert!(actual.is_err());
+        }
+
+        #[test]
+        fn fail_deny() {
+            let role = deployer(vec![
+                principal_policy(Effect::Allow, &["*"], "sts:assume:role", "*"),
+                principal_policy(Effect::Deny, &["user:alice"], "*", "*"),
+            ]);
+
+            let actual = role.assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR);
+
+            assert!(actual.is_err());
+        }
+    }
+
+    mod is_authorized {
+        use super::*;
+
+        fn evaluate(now: SystemTime) -> Effect {
+            let role = deployer(vec![principal_policy(Effect::Allow, &["*"], "*", "*")]);
+            let assumed = role
+                .assume(&principal("user:alice"), SystemTime::UNIX_EPOCH, HOUR)
+                .unwrap();
+
+            let action = ScopedAction::parse("deploy:start:service").unwrap();
+            let resources = vec![ScopedResource::parse("deploy:service/api").unwrap()];
+
+            assumed.is_authorized(now, &action, &resources).0
+        }
+
+        #[test]
+        fn pass() {
+            assert_eq!(evaluate(SystemTime::UNIX_EPOCH), Effect::Allow);
+        }
+
+        #[test]
+        fn fail_expired() {
+            assert_eq!(evaluate(SystemTime::UNIX_EPOCH + HOUR), Effect::Deny);
+        }
+    }
+}
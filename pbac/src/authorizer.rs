@@ -1,48 +1,142 @@
 use crate::action::ScopedAction;
-use crate::{Effect, Element, Policy, ScopedResource};
+use crate::combining::{CombiningAlgorithm, Decision, DenyOverrides};
+use crate::{Effect, Element, Policy, Principal, ScopedResource};
 
+/// The ResourceTypeMismatch describes a resource that the action cannot act upon.
+#[derive(Debug, PartialEq)]
+pub struct ResourceTypeMismatch {
+    /// The action, declaring its scope and resource type.
+    pub action: String,
+
+    /// The resource, whose scope or type differs from the action's.
+    pub resource: String,
+}
+
+/// Evaluates the policies against the action and resources, combining them with deny-overrides and
+/// denying when no policy applies.
+///
+/// A request with a typed resource that does not fit the action, as reported by
+/// `check_resource_types`, is denied. Untyped resources are not checked.
 pub fn is_authorized<'a>(
     policies: &'a [Policy],
     scoped_action: &ScopedAction,
     scoped_resources: &[ScopedResource],
 ) -> (Effect, Vec<&'a Policy>) {
-    let policy_matches = policies
+    let (decision, policies) = evaluate(&DenyOverrides, policies, scoped_action, scoped_resources);
+
+    (decision.effect(), policies)
+}
+
+/// Evaluates the policies against the action and resources, combining the decisions of the
+/// policies with the algorithm.
+///
+/// A policy applies when any of its actions matches the action and any of its resources matches
+/// any of the resources, otherwise its decision is `NotApplicable`. A request with a typed resource
+/// that does not fit the action is `Indeterminate`, whatever the policies.
+pub fn evaluate<'a>(
+    algorithm: &dyn CombiningAlgorithm,
+    policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> (Decision, Vec<&'a Policy>) {
+    if !is_typed(scoped_action, scoped_resources) {
+        return (Decision::Indeterminate, vec![]);
+    }
+
+    combine(algorithm, policies, |policy| {
+        is_applicable(policy, scoped_action, scoped_resources)
+    })
+}
+
+/// Combines the effects of the policies that are applicable with the algorithm, the others being
+/// `NotApplicable`.
+pub(crate) fn combine<'a>(
+    algorithm: &dyn CombiningAlgorithm,
+    policies: &'a [Policy],
+    is_applicable: impl Fn(&Policy) -> bool,
+) -> (Decision, Vec<&'a Policy>) {
+    let decisions = policies
         .iter()
-        .filter(|policy| {
-            let action_match = policy
-                .actions
-                .iter()
-                .any(|document| document.is_match(scoped_action));
-
-            let resource_match = policy.resources.iter().any(|document| {
-                scoped_resources
-                    .iter()
-                    .any(|scoped_resource| document.is_match(scoped_resource))
-            });
-
-            action_match && resource_match
+        .map(|policy| {
+            if is_applicable(policy) {
+                policy.effect.into()
+            } else {
+                Decision::NotApplicable
+            }
         })
         .collect::<Vec<_>>();
 
-    let denied_policy_matches = policy_matches
-        .iter()
-        .filter(|&policy| policy.effect == Effect::Deny)
-        .copied()
-        .collect::<Vec<_>>();
+    let (decision, indices) = algorithm.combine(&decisions);
+
+    (
+        decision,
+        indices.into_iter().map(|index| &policies[index]).collect(),
+    )
+}
 
-    if !denied_policy_matches.is_empty() {
-        return (Effect::Deny, denied_policy_matches);
+/// Returns whether the policy applies to the action and resources. No policy applies to a request
+/// with a typed resource that does not fit the action.
+pub(crate) fn is_applicable(
+    policy: &Policy,
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> bool {
+    if !is_typed(scoped_action, scoped_resources) {
+        return false;
     }
 
-    let allowed_policy_matches = policy_matches
+    let action_match = policy
+        .actions
         .iter()
-        .filter(|&policy| policy.effect == Effect::Allow)
-        .copied()
-        .collect::<Vec<_>>();
+        .any(|document| document.is_match(scoped_action));
 
-    if !allowed_policy_matches.is_empty() {
-        return (Effect::Allow, allowed_policy_matches);
-    }
+    let resource_match = policy.resources.iter().any(|document| {
+        scoped_resources
+            .iter()
+            .any(|scoped_resource| document.is_match(scoped_resource))
+    });
+
+    action_match && resource_match
+}
+
+/// Returns whether the policy applies to any of the principals. A policy without principals, or
+/// with the `*` principal, applies to every principal.
+pub(crate) fn is_principal(policy: &Policy, principals: &[Principal]) -> bool {
+    policy.principals.is_empty()
+        || policy
+            .principals
+            .iter()
+            .any(|principal| principal.0 == "*" || principals.contains(principal))
+}
+
+/// Returns the resources that are not of the scope and type the action declares.
+///
+/// The action `docs:read:document` acts upon resources of scope `docs` and type `document`. An
+/// untyped resource only needs to share the action's scope.
+pub fn check_resource_types(
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> Vec<ResourceTypeMismatch> {
+    scoped_resources
+        .iter()
+        .filter(|scoped_resource| !fits(scoped_action, scoped_resource))
+        .map(|scoped_resource| ResourceTypeMismatch {
+            action: scoped_action.to_string(),
+            resource: scoped_resource.to_string(),
+        })
+        .collect()
+}
+
+/// Returns whether every typed resource fits the action, as `check_resource_types` reports.
+pub(crate) fn is_typed(scoped_action: &ScopedAction, scoped_resources: &[ScopedResource]) -> bool {
+    scoped_resources.iter().all(|scoped_resource| {
+        scoped_resource.resource_type.is_none() || fits(scoped_action, scoped_resource)
+    })
+}
 
-    (Effect::Deny, vec![])
+fn fits(scoped_action: &ScopedAction, scoped_resource: &ScopedResource) -> bool {
+    scoped_resource.scope == scoped_action.scope
+        && scoped_resource
+            .resource_type()
+            .is_none_or(|resource_type| resource_type == scoped_action.action.resource)
 }
@@ -0,0 +1,187 @@
+use crate::{is_authorized, Effect, Policy, ScopedAction, ScopedResource};
+
+/// The BoundaryDecision describes the effect of identity policies capped by a permission boundary.
+#[derive(Debug, PartialEq)]
+pub struct BoundaryDecision<'a> {
+    pub effect: Effect,
+
+    /// The identity and boundary policies that determined the effect.
+    pub policies: Vec<&'a Policy>,
+
+    /// Whether the identity policies allowed the request, but the boundary did not.
+    pub capped: bool,
+}
+
+/// Evaluates the identity policies of a principal, allowing only what the permission boundary
+/// attached to the principal also allows.
+///
+/// The boundary grants nothing by itself, it sets the maximum permissions the identity policies
+/// can grant. An explicit deny in either the identity policies or the boundary wins.
+pub fn is_authorized_within_boundary<'a>(
+    identity_policies: &'a [Policy],
+    boundary_policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> BoundaryDecision<'a> {
+    let (effect, policies, capped) = intersect(
+        identity_policies,
+        boundary_policies,
+        scoped_action,
+        scoped_resources,
+    );
+
+    BoundaryDecision {
+        effect,
+        policies,
+        capped,
+    }
+}
+
+/// Allows only what both the granting and the limiting policies allow, letting an explicit deny in
+/// either win. Returns the effect, the policies that determined it, and whether the granting
+/// policies allowed the request but the limiting policies did not.
+pub(crate) fn intersect<'a>(
+    granting_policies: &'a [Policy],
+    limiting_policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> (Effect, Vec<&'a Policy>, bool) {
+    let (granting_effect, granting_matches) =
+        is_authorized(granting_policies, scoped_action, scoped_resources);
+    let (limiting_effect, limiting_matches) =
+        is_authorized(limiting_policies, scoped_action, scoped_resources);
+
+    let is_explicit =
+        |effect: Effect, matches: &[&Policy]| effect == Effect::Deny && !matches.is_empty();
+
+    let policies = match (
+        is_explicit(granting_effect, &granting_matches),
+        is_explicit(limiting_effect, &limiting_matches),
+    ) {
+        (true, true) => [granting_matches, limiting_matches].concat(),
+        (true, false) => granting_matches,
+        (false, true) => limiting_matches,
+        (false, false) if granting_effect == Effect::Allow && limiting_effect == Effect::Allow => {
+            return (
+                Effect::Allow,
+                [granting_matches, limiting_matches].concat(),
+                false,
+            );
+        }
+        (false, false) => vec![],
+    };
+
+    (Effect::Deny, policies, granting_effect == Effect::Allow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+
+    fn evaluate<'a>(
+        identity_policies: &'a [Policy],
+        boundary_policies: &'a [Policy],
+        action: &str,
+    ) -> BoundaryDecision<'a> {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse("scope:resource").unwrap()];
+
+        is_authorized_within_boundary(identity_policies, boundary_policies, &action, &resources)
+    }
+
+    mod is_authorized_within_boundary {
+        use super::*;
+
+        #[test]
+        fn pass_within() {
+            let identity = vec![policy(Effect::Allow, "scope:read:*", "*")];
+            let boundary = vec![policy(Effect::Allow, "scope:*", "*")];
+
+            let actual = evaluate(&identity, &boundary, "scope:read:resource");
+
+            assert_eq!(
+                actual,
+                BoundaryDecision {
+                    effect: Effect::Allow,
+                    policies: vec![&identity[0], &boundary[0]],
+                    capped: false,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_beyond() {
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+            let boundary = vec![policy(Effect::Allow, "scope:read:*", "*")];
+
+            let actual = evaluate(&identity, &boundary, "scope:write:resource");
+
+            assert_eq!(
+                actual,
+                BoundaryDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    capped: true,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_boundary_does_not_grant() {
+            let identity = vec![];
+            let boundary = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(&identity, &boundary, "scope:read:resource");
+
+            assert_eq!(
+                actual,
+                BoundaryDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    capped: false,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_identity_deny() {
+            let identity = vec![
+                policy(Effect::Allow, "*", "*"),
+                policy(Effect::Deny, "scope:read:*", "*"),
+            ];
+            let boundary = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(&identity, &boundary, "scope:read:resource");
+
+            assert_eq!(
+                actual,
+                BoundaryDecision {
+                    effect: Effect::Deny,
+                    policies: vec![&identity[1]],
+                    capped: false,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_boundary_deny() {
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+            let boundary = vec![
+                policy(Effect::Allow, "*", "*"),
+                policy(Effect::Deny, "scope:read:*", "*"),
+            ];
+
+            let actual = evaluate(&identity, &boundary, "scope:read:resource");
+
+            assert_eq!(
+                actual,
+                BoundaryDecision {
+                    effect: Effect::Deny,
+                    policies: vec![&boundary[1]],
+                    capped: true,
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use crate::{ActionDocument, Part, Policy, ScopedAction};
+use std::collections::BTreeSet;
+
+/// The ActionCatalog describes the actions that services have registered.
+#[derive(Debug, Default)]
+pub struct ActionCatalog {
+    actions: BTreeSet<(String, String, String)>,
+}
+
+/// The UnknownAction describes an action that no registered action matches.
+#[derive(Debug, PartialEq)]
+pub struct UnknownAction {
+    /// The action, or action document, that was validated.
+    pub action: String,
+
+    /// The first part of the action that no registered action matches.
+    pub part: Part,
+
+    /// The value of the unknown part.
+    pub value: String,
+
+    /// The closest registered value for the unknown part, if any is close enough.
+    pub suggestion: Option<String>,
+}
+
+impl ActionCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the `verb` on the `resource` type of the `scope`.
+    pub fn register(&mut self, scope: &str, verb: &str, resource: &str) {
+        self.actions
+            .insert((scope.to_string(), verb.to_string(), resource.to_string()));
+    }
+
+    /// Checks that the action has been registered.
+    pub fn validate_action(&self, action: &ScopedAction) -> Result<(), UnknownAction> {
+        self.validate(
+            action.to_string(),
+            [
+                Some(&action.scope),
+                Some(&action.action.verb),
+                Some(&action.action.resource),
+            ],
+        )
+    }
+
+    /// Checks that the document matches at least one registered action.
+    pub fn validate_document(&self, document: &ActionDocument) -> Result<(), UnknownAction> {
+        self.validate(document.to_string(), document.pattern())
+    }
+
+    /// Returns the action documents of the policy that match no registered action.
+    pub fn validate_policy(&self, policy: &Policy) -> Vec<UnknownAction> {
+        policy
+            .actions
+            .iter()
+            .filter_map(|document| self.validate_document(document).err())
+            .collect()
+    }
+
+    fn validate(&self, action: String, pattern: [Option<&str>; 3]) -> Result<(), UnknownAction> {
+        let mut candidates = self
+            .actions
+            .iter()
+            .map(|(scope, verb, resource)| [scope.as_str(), verb.as_str(), resource.as_str()])
+            .collect::<Vec<_>>();
+
+        for (index, part) in [Part::Scope, Part::Verb, Part::Resource].iter().enumerate() {
+            let value = match pattern[index] {
+                None => continue,
+                Some(value) => value,
+            };
+
+            let matches = candidates
+                .iter()
+                .filter(|candidate| candidate[index] == value)
+                .copied()
+                .collect::<Vec<_>>();
+
+            if matches.is_empty() {
+                return Err(UnknownAction {
+                    action,
+                    part: *part,
+                    value: value.to_string(),
+                    suggestion: suggest(value, candidates.iter().map(|c| c[index])),
+                });
+            }
+
+            candidates = matches;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the candidate closest to the value, if it is within a third of the value's length.
+fn suggest<'a>(value: &str, candidates: impl Iterator<Item = &'a str>) -> Option<String> {
+    let threshold = (value.chars().count() / 3).max(1);
+
+    candidates
+        .map(|candidate| (distance(value, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Returns the optimal string alignment distance, which counts insertions, deletions,
+/// substitutions and transpositions of adjacent characters.
+fn distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Effect, Element, ResourceDocument};
+
+    fn catalog() -> ActionCatalog {
+        let mut catalog = ActionCatalog::new();
+        catalog.register("billing", "read", "invoice");
+        catalog.register("billing", "write", "invoice");
+        catalog.register("docs", "read", "document");
+        catalog
+    }
+
+    mod validate_action {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let action = ScopedAction::parse("billing:read:invoice").unwrap();
+
+            let actual = catalog().validate_action(&action);
+
+            assert_eq!(actual, Ok(()));
+        }
+
+        #[test]
+        fn fail_scope() {
+            let expected = UnknownAction {
+                action: "biling:read:invoice".to_string(),
+                part: Part::Scope,
+                value: "biling".to_string(),
+                suggestion: Some("billing".to_string()),
+            };
+
+            let action = ScopedAction::parse("biling:read:invoice").unwrap();
+
+            let actual = catalog().validate_action(&action).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_resource_of_other_scope() {
+            let expected = UnknownAction {
+                action: "billing:read:document".to_string(),
+                part: Part::Resource,
+                value: "document".to_string(),
+                suggestion: None,
+            };
+
+            let action = ScopedAction::parse("billing:read:document").unwrap();
+
+            let actual = catalog().validate_action(&action).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod validate_document {
+        use super::*;
+
+        #[test]
+        fn pass_wildcard() {
+            for value in &["*", "billing:*", "*:read:*", "*:*:document"] {
+                let document = ActionDocument::parse(value).unwrap();
+
+                let actual = catalog().validate_document(&document);
+
+                assert_eq!(actual, Ok(()));
+            }
+        }
+
+        #[test]
+        fn fail_verb() {
+            let expected = UnknownAction {
+                action: "billing:raed:*".to_string(),
+                part: Part::Verb,
+                value: "raed".to_string(),
+                suggestion: Some("read".to_string()),
+            };
+
+            let document = ActionDocument::parse("billing:raed:*").unwrap();
+
+            let actual = catalog().validate_document(&document).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_no_suggestion() {
+            let expected = UnknownAction {
+                action: "*:purge:*".to_string(),
+                part: Part::Verb,
+                value: "purge".to_string(),
+                suggestion: None,
+            };
+
+            let document = ActionDocument::parse("*:purge:*").unwrap();
+
+            let actual = catalog().validate_document(&document).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod validate_policy {
+        use super::*;
+
+        #[test]
+        fn fail_documents() {
+            let policy = Policy {
+                actions: vec![
+                    ActionDocument::parse("billing:read:*").unwrap(),
+                    ActionDocument::parse("docs:reed:document").unwrap(),
+                    ActionDocument::parse("billing:write:invoices").unwrap(),
+                ],
+                effect: Effect::Allow,
+                principals: vec![],
+                resources: vec![ResourceDocument::parse("*").unwrap()],
+            };
+
+            let actual = catalog().validate_policy(&policy);
+
+            assert_eq!(actual.len(), 2);
+            assert_eq!(actual[0].suggestion, Some("read".to_string()));
+            assert_eq!(actual[1].suggestion, Some("invoice".to_string()));
+        }
+    }
+
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            assert_eq!(distance("read", "read"), 0);
+            assert_eq!(distance("raed", "read"), 1);
+            assert_eq!(distance("red", "read"), 1);
+            assert_eq!(distance("write", "read"), 4);
+            assert_eq!(distance("", "read"), 4);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::Effect;
+use std::fmt;
+
+/// The Decision describes the outcome of evaluating a policy, or of combining several.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Decision {
+    Allow,
+    Deny,
+
+    /// No policy applies to the request.
+    NotApplicable,
+
+    /// The policies could not be combined into a decision, such as when more than one applies
+    /// under only-one-applicable.
+    Indeterminate,
+}
+
+/// The CombiningAlgorithm describes how the decisions of several policies combine into one.
+pub trait CombiningAlgorithm: fmt::Debug {
+    /// Combines the decisions, given in policy order, returning the decision along with the indices
+    /// of the decisions that determined it.
+    fn combine(&self, decisions: &[Decision]) -> (Decision, Vec<usize>);
+}
+
+/// The DenyOverrides allows when any decision allows, unless any decision denies.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DenyOverrides;
+
+/// The PermitOverrides denies when any decision denies, unless any decision allows.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PermitOverrides;
+
+/// The FirstApplicable takes the first decision that is not `NotApplicable`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FirstApplicable;
+
+/// The OnlyOneApplicable takes the decision of the only applicable policy, and is
+/// `Indeterminate` when more than one applies.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OnlyOneApplicable;
+
+/// The DenyUnlessPermit allows when any decision allows, and denies otherwise.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DenyUnlessPermit;
+
+impl Decision {
+    /// Returns the effect to enforce, denying anything that is not explicitly allowed.
+    pub fn effect(self) -> Effect {
+        match self {
+            Decision::Allow => Effect::Allow,
+            _ => Effect::Deny,
+        }
+    }
+}
+
+impl From<Effect> for Decision {
+    fn from(effect: Effect) -> Self {
+        match effect {
+            Effect::Allow => Decision::Allow,
+            Effect::Deny => Decision::Deny,
+        }
+    }
+}
+
+impl CombiningAlgorithm for DenyOverrides {
+    fn combine(&self, decisions: &[Decision]) -> (Decision, Vec<usize>) {
+        first_of(
+            decisions,
+            &[Decision::Deny, Decision::Indeterminate, Decision::Allow],
+        )
+    }
+}
+
+impl CombiningAlgorithm for PermitOverrides {
+    fn combine(&self, decisions: &[Decision]) -> (Decision, Vec<usize>) {
+        first_of(
+            decisions,
+            &[Decision::Allow, Decision::Indeterminate, Decision::Deny],
+        )
+    }
+}
+
+impl CombiningAlgorithm for FirstApplicable {
+    fn combine(&self, decisions: &[Decision]) -> (Decision, Vec<usize>) {
+        decisions
+            .iter()
+            .position(|&decision| decision != Decision::NotApplicable)
+            .map_or((Decision::NotApplicable, vec![]), |index| {
+                (decisions[index], vec![index])
+            })
+    }
+}
+
+impl CombiningAlgorithm for OnlyOneApplicable {
+    fn combine(&self, decisions: &[Decision]) -> (Decision, Vec<usize>) {
+        let applicable = indices(decisions, |decision| decision != Decision::NotApplicable);
+
+        match applicable.as_slice() {
+            [] => (Decision::NotApplicable, vec![]),
+            [index] => (decisions[*index], applicable),
+            _ => (Decision::Indeterminate, applicable),
+        }
+    }
+}
+
+impl CombiningAlgorithm for DenyUnlessPermit {
+    fn combine(&self, decisions: &[Decision]) -> (Decision, Vec<usize>) {
+        match first_of(decisions, &[Decision::Allow, Decision::Deny]) {
+            (Decision::NotApplicable, _) => (Decision::Deny, vec![]),
+            combined => combined,
+        }
+    }
+}
+
+/// Returns the first of the decisions, in order of precedence, that any decision has, along with
+/// the indices of the decisions that have it.
+fn first_of(decisions: &[Decision], precedence: &[Decision]) -> (Decision, Vec<usize>) {
+    precedence
+        .iter()
+        .map(|&decision| (decision, indices(decisions, |other| other == decision)))
+        .find(|(_, indices)| !indices.is_empty())
+        .unwrap_or((Decision::NotApplicable, vec![]))
+}
+
+fn indices(decisions: &[Decision], predicate: impl Fn(Decision) -> bool) -> Vec<usize> {
+    decisions
+        .iter()
+        .enumerate()
+        .filter(|(_, &decision)| predicate(decision))
+        .map(|(index, _)| index)
+        .collect()
+}
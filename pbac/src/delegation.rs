@@ -0,0 +1,252 @@
+use crate::authorizer::is_principal;
+use crate::{diff, Change, Effect, Policy, Principal, PrincipalResolver};
+use std::error::Error;
+use std::fmt;
+
+/// The DelegationError describes why the grantor may not propose a policy.
+#[derive(Debug, PartialEq)]
+pub enum DelegationError {
+    /// The policy grants more than the grantor holds.
+    ExcessiveGrant {
+        grantor: Principal,
+
+        /// The regions of requests that the policy allows but the grantor is denied.
+        excess: Vec<Change>,
+    },
+
+    /// The policy allows without naming the principals it grants to, or names `*`, so that it
+    /// would grant to every principal.
+    UnnamedGrantee { grantor: Principal },
+}
+
+/// Checks that the policy proposed by the grantor, such as a team lead granting rights to their
+/// reports, grants a subset of what the grantor holds.
+///
+/// Only the effective policies that apply to the grantor, or to any group it belongs to, count
+/// towards what the grantor holds. An allowing policy must name the principals it grants to, and
+/// may grant each of them no more than the grantor holds. A policy that denies never exceeds its
+/// grantor.
+///
+/// The check is symbolic over the patterns of the documents, as in `check_subset`, and is a proof
+/// over every request rather than a sample. As there, requests are taken to carry a single
+/// resource.
+pub fn delegate(
+    resolver: &dyn PrincipalResolver,
+    grantor: &Principal,
+    effective_policies: &[Policy],
+    policy: &Policy,
+) -> Result<(), DelegationError> {
+    let named = !policy.principals.is_empty()
+        && policy.principals.iter().all(|principal| principal.0 != "*");
+
+    if policy.effect == Effect::Allow && !named {
+        return Err(DelegationError::UnnamedGrantee {
+            grantor: grantor.clone(),
+        });
+    }
+
+    let principals = resolver.resolve(grantor);
+    let held = effective_policies
+        .iter()
+        .filter(|policy| is_principal(policy, &principals))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let excess = diff(&held, std::slice::from_ref(policy)).allowed;
+
+    if excess.is_empty() {
+        Ok(())
+    } else {
+        Err(DelegationError::ExcessiveGrant {
+            grantor: grantor.clone(),
+            excess,
+        })
+    }
+}
+
+impl fmt::Display for DelegationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DelegationError::ExcessiveGrant { grantor, excess } => {
+                write!(
+                    f,
+                    "{} may not grant rights they do not hold, such as",
+                    grantor.0
+                )?;
+
+                for (index, change) in excess.iter().enumerate() {
+                    let separator = if index == 0 { " " } else { ", " };
+
+                    write!(
+                        f,
+                        "{}{} on {}",
+                        separator, change.example.action, change.example.resource
+                    )?;
+                }
+
+                Ok(())
+            }
+            DelegationError::UnnamedGrantee { grantor } => {
+                write!(
+                    f,
+                    "{} may only grant rights to the principals they name",
+                    grantor.0
+                )
+            }
+        }
+    }
+}
+
+impl Error for DelegationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+    use crate::{InMemoryPrincipalResolver, Segment, Segments};
+
+    /// The policy granted to the reports of the lead.
+    fn proposal(effect: Effect, action: &str, resource: &str) -> Policy {
+        Policy {
+            principals: vec![Principal("group:reports".to_string())],
+            ..policy(effect, action, resource)
+        }
+    }
+
+    fn lead() -> Principal {
+        Principal("user:lead".to_string())
+    }
+
+    fn resolver() -> InMemoryPrincipalResolver {
+        let mut resolver = InMemoryPrincipalResolver::new();
+        resolver.add_member("group:leads", "user:lead");
+        resolver
+    }
+
+    fn team() -> Segment {
+        Segment::Value("team".to_string())
+    }
+
+    /// The lead may do anything to the team's documents but delete them, through the leads.
+    fn effective_policies() -> Vec<Policy> {
+        vec![
+            Policy {
+                principals: vec![Principal("group:leads".to_string())],
+                ..policy(Effect::Allow, "docs:*", "docs:team")
+            },
+            policy(Effect::Deny, "docs:delete:*", "*"),
+            Policy {
+                principals: vec![Principal("user:admin".to_string())],
+                ..policy(Effect::Allow, "*", "*")
+            },
+        ]
+    }
+
+    fn excess(actual: Result<(), DelegationError>) -> Vec<Change> {
+        match actual {
+            Err(DelegationError::ExcessiveGrant { grantor, excess }) => {
+                assert_eq!(grantor, lead());
+                excess
+            }
+            actual => panic!("expected an excessive grant, got {:?}", actual),
+        }
+    }
+
+    mod delegate {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let proposed = proposal(Effect::Allow, "docs:read:*", "docs:team");
+
+            let actual = delegate(&resolver(), &lead(), &effective_policies(), &proposed);
+
+            assert_eq!(actual, Ok(()));
+        }
+
+        #[test]
+        fn pass_deny() {
+            let proposed = policy(Effect::Deny, "*", "*");
+
+            let actual = delegate(&resolver(), &lead(), &effective_policies(), &proposed);
+
+            assert_eq!(actual, Ok(()));
+        }
+
+        #[test]
+        fn fail_excess() {
+            let proposed = proposal(Effect::Allow, "docs:*", "docs:team");
+
+            let actual = excess(delegate(
+                &resolver(),
+                &lead(),
+                &effective_policies(),
+                &proposed,
+            ));
+
+            assert_eq!(actual.len(), 1);
+            assert_eq!(actual[0].region.verb, Segment::Value("delete".to_string()));
+        }
+
+        #[test]
+        fn fail_resource() {
+            let proposed = proposal(Effect::Allow, "docs:read:*", "*");
+
+            let actual = excess(delegate(
+                &resolver(),
+                &lead(),
+                &effective_policies(),
+                &proposed,
+            ));
+
+            assert!(!actual.is_empty());
+            for change in &actual {
+                assert_eq!(change.region.verb, Segment::Value("read".to_string()));
+                assert_ne!(change.region.segments, Segments::Exactly(vec![team()]));
+            }
+        }
+
+        #[test]
+        fn fail_other_principal() {
+            let proposed = proposal(Effect::Allow, "billing:read:*", "*");
+
+            let actual = excess(delegate(
+                &resolver(),
+                &lead(),
+                &effective_policies(),
+                &proposed,
+            ));
+
+            assert!(!actual.is_empty());
+        }
+
+        #[test]
+        fn fail_unnamed() {
+            let expected = Err(DelegationError::UnnamedGrantee { grantor: lead() });
+
+            for principals in &[vec![], vec![Principal("*".to_string())]] {
+                let proposed = Policy {
+                    principals: principals.clone(),
+                    ..policy(Effect::Allow, "docs:read:*", "docs:team")
+                };
+
+                let actual = delegate(&resolver(), &lead(), &effective_policies(), &proposed);
+
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn fail_no_rights() {
+            let proposed = proposal(Effect::Allow, "docs:read:*", "docs:team");
+
+            let actual = delegate(&resolver(), &lead(), &[], &proposed).unwrap_err();
+
+            assert_eq!(
+                actual.to_string(),
+                "user:lead may not grant rights they do not hold, such as \
+                 docs:read:x on docs:team"
+            );
+        }
+    }
+}
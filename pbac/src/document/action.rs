@@ -1,9 +1,11 @@
 use crate::action::Action;
 use crate::document::wildcard::WildcardToken;
 use crate::document::Element;
-use crate::{ElementParseError, ScopedAction};
+use crate::parser::Parser;
+use crate::{ActionImplications, ElementParseError, Part, ScopedAction};
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ActionDocument {
     scoped_action: WildcardToken<ScopedActionToken>,
 }
@@ -15,12 +17,69 @@ impl Element<ScopedAction> for ActionDocument {
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
         Ok(Self {
-            scoped_action: WildcardToken::<ScopedActionToken>::parse(value)?,
+            scoped_action: WildcardToken::<ScopedActionToken>::read(&mut Parser::new(value))?,
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl ActionDocument {
+    /// Returns the scope, verb and resource patterns, where `None` is a wildcard.
+    pub(crate) fn pattern(&self) -> [Option<&str>; 3] {
+        match &self.scoped_action {
+            WildcardToken::Wildcard => [None, None, None],
+            WildcardToken::Value(scoped_action) => {
+                let (verb, resource) = match &scoped_action.action {
+                    WildcardToken::Wildcard => (None, None),
+                    WildcardToken::Value(action) => (action.verb.value(), action.resource.value()),
+                };
+
+                [scoped_action.scope.value(), verb, resource]
+            }
+        }
+    }
+
+    /// Returns the chain of verbs through which the document matches the action, from the verb the
+    /// document grants down to the verb of the action, or `None` if it does not match. A wildcard,
+    /// or the verb itself, matches with a chain of just the verb of the action.
+    pub fn implied_match(
+        &self,
+        value: &ScopedAction,
+        implications: &ActionImplications,
+    ) -> Option<Vec<String>> {
+        match &self.scoped_action {
+            WildcardToken::Wildcard => Some(vec![value.action.verb.clone()]),
+            WildcardToken::Value(scoped_action) => {
+                if !scoped_action.scope.is_match(&value.scope) {
+                    return None;
+                }
+
+                match &scoped_action.action {
+                    WildcardToken::Wildcard => Some(vec![value.action.verb.clone()]),
+                    WildcardToken::Value(action) => {
+                        if !action.resource.is_match(&value.action.resource) {
+                            return None;
+                        }
+
+                        match &action.verb {
+                            WildcardToken::Wildcard => Some(vec![value.action.verb.clone()]),
+                            WildcardToken::Value(verb) => {
+                                implications.chain(&value.scope, verb, &value.action.verb)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ActionDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.scoped_action.fmt(f)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 struct ScopedActionToken {
     scope: WildcardToken<String>,
     action: WildcardToken<ActionToken>,
@@ -32,22 +91,34 @@ impl Element<ScopedAction> for ScopedActionToken {
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value.find(':') {
-            None => Err(ElementParseError {
-                token: value.to_string(),
-            }),
-            Some(position) => {
-                let scope = &value[0..position];
-                let action = &value[position + 1..value.len()];
-
-                Ok(Self {
-                    scope: WildcardToken::<String>::parse(scope)?,
-                    action: WildcardToken::<ActionToken>::parse(action)?,
-                })
-            }
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl ScopedActionToken {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(Self {
+            scope: parser.segment(Part::Scope)?.into(),
+            action: WildcardToken::<ActionToken>::read(parser)?,
+        })
+    }
+}
+
+impl fmt::Display for ScopedActionToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.scope, self.action)
+    }
+}
+
+impl fmt::Display for WildcardToken<ScopedActionToken> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildcardToken::Wildcard => f.write_str("*"),
+            WildcardToken::Value(document) => document.fmt(f),
         }
     }
 }
+
 impl Element<ScopedAction> for WildcardToken<ScopedActionToken> {
     fn is_match(&self, value: &ScopedAction) -> bool {
         match self {
@@ -57,14 +128,21 @@ impl Element<ScopedAction> for WildcardToken<ScopedActionToken> {
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        Ok(match value {
-            "*" => Self::Wildcard,
-            value => Self::Value(ScopedActionToken::parse(value)?),
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl WildcardToken<ScopedActionToken> {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(if parser.wildcard() {
+            Self::Wildcard
+        } else {
+            Self::Value(ScopedActionToken::read(parser)?)
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 struct ActionToken {
     verb: WildcardToken<String>,
     resource: WildcardToken<String>,
@@ -76,19 +154,30 @@ impl Element<Action> for ActionToken {
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value.find(':') {
-            None => Err(ElementParseError {
-                token: value.to_string(),
-            }),
-            Some(position) => {
-                let verb = &value[0..position];
-                let resource = &value[position + 1..value.len()];
-
-                Ok(Self {
-                    verb: WildcardToken::<String>::parse(verb)?,
-                    resource: WildcardToken::<String>::parse(resource)?,
-                })
-            }
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl ActionToken {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(Self {
+            verb: parser.segment(Part::Verb)?.into(),
+            resource: parser.last(Part::Resource)?.into(),
+        })
+    }
+}
+
+impl fmt::Display for ActionToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.verb, self.resource)
+    }
+}
+
+impl fmt::Display for WildcardToken<ActionToken> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildcardToken::Wildcard => f.write_str("*"),
+            WildcardToken::Value(document) => document.fmt(f),
         }
     }
 }
@@ -102,9 +191,16 @@ impl Element<Action> for WildcardToken<ActionToken> {
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        Ok(match value {
-            "*" => Self::Wildcard,
-            value => Self::Value(ActionToken::parse(value)?),
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl WildcardToken<ActionToken> {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(if parser.wildcard() {
+            Self::Wildcard
+        } else {
+            Self::Value(ActionToken::read(parser)?)
         })
     }
 }
@@ -126,6 +222,7 @@ mod tests {
      *      *:*
      */
     use super::*;
+    use crate::ParseErrorKind;
 
     mod parse {
         use super::*;
@@ -147,7 +244,10 @@ mod tests {
             #[test]
             fn fail_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: "".to_string(),
                 };
 
                 let actual = ActionDocument::parse("").unwrap_err();
@@ -158,7 +258,10 @@ mod tests {
             #[test]
             fn fail_token() {
                 let expected = ElementParseError {
-                    token: "token".to_string(),
+                    kind: ParseErrorKind::MissingSeparator,
+                    offset: 5,
+                    part: Part::Scope,
+                    input: "token".to_string(),
                 };
 
                 let actual = ActionDocument::parse("token").unwrap_err();
@@ -190,7 +293,10 @@ mod tests {
             #[test]
             fn fail_scope_verb_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 11,
+                    part: Part::Resource,
+                    input: "scope:verb:".to_string(),
                 };
 
                 let actual = ActionDocument::parse("scope:verb:").unwrap_err();
@@ -201,7 +307,10 @@ mod tests {
             #[test]
             fn fail_scope_empty_resource() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 6,
+                    part: Part::Verb,
+                    input: "scope::resource".to_string(),
                 };
 
                 let actual = ActionDocument::parse("scope::resource").unwrap_err();
@@ -212,7 +321,10 @@ mod tests {
             #[test]
             fn fail_scope_empty_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 6,
+                    part: Part::Verb,
+                    input: "scope::".to_string(),
                 };
 
                 let actual = ActionDocument::parse("scope::").unwrap_err();
@@ -223,7 +335,10 @@ mod tests {
             #[test]
             fn fail_empty_verb_resource() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":verb:resource".to_string(),
                 };
 
                 let actual = ActionDocument::parse(":verb:resource").unwrap_err();
@@ -234,7 +349,10 @@ mod tests {
             #[test]
             fn fail_empty_verb_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":verb:".to_string(),
                 };
 
                 let actual = ActionDocument::parse(":verb:").unwrap_err();
@@ -245,7 +363,10 @@ mod tests {
             #[test]
             fn fail_empty_empty_resource() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: "::resource".to_string(),
                 };
 
                 let actual = ActionDocument::parse("::resource").unwrap_err();
@@ -256,7 +377,10 @@ mod tests {
             #[test]
             fn fail_empty_empty_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: "::".to_string(),
                 };
 
                 let actual = ActionDocument::parse("::").unwrap_err();
@@ -288,7 +412,10 @@ mod tests {
             #[test]
             fn fail_scope_empty_wildcard() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 6,
+                    part: Part::Verb,
+                    input: "scope::*".to_string(),
                 };
 
                 let actual = ActionDocument::parse("scope::*").unwrap_err();
@@ -299,7 +426,10 @@ mod tests {
             #[test]
             fn fail_empty_verb_wildcard() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":verb:*".to_string(),
                 };
 
                 let actual = ActionDocument::parse(":verb:*").unwrap_err();
@@ -310,7 +440,10 @@ mod tests {
             #[test]
             fn fail_empty_empty_wildcard() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: "::*".to_string(),
                 };
 
                 let actual = ActionDocument::parse("::*").unwrap_err();
@@ -342,7 +475,10 @@ mod tests {
             #[test]
             fn fail_scope_wildcard_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 8,
+                    part: Part::Resource,
+                    input: "scope:*:".to_string(),
                 };
 
                 let actual = ActionDocument::parse("scope:*:").unwrap_err();
@@ -353,7 +489,10 @@ mod tests {
             #[test]
             fn fail_empty_wildcard_resource() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":*:resource".to_string(),
                 };
 
                 let actual = ActionDocument::parse(":*:resource").unwrap_err();
@@ -364,7 +503,10 @@ mod tests {
             #[test]
             fn fail_empty_wildcard_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":*:".to_string(),
                 };
 
                 let actual = ActionDocument::parse(":*:").unwrap_err();
@@ -393,7 +535,10 @@ mod tests {
             #[test]
             fn fail_empty_wildcard() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":*".to_string(),
                 };
 
                 let actual = ActionDocument::parse(":*").unwrap_err();
@@ -425,7 +570,10 @@ mod tests {
             #[test]
             fn fail_wildcard_verb_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 7,
+                    part: Part::Resource,
+                    input: "*:verb:".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*:verb:").unwrap_err();
@@ -436,7 +584,10 @@ mod tests {
             #[test]
             fn fail_wildcard_empty_resource() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 2,
+                    part: Part::Verb,
+                    input: "*::resource".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*::resource").unwrap_err();
@@ -447,7 +598,10 @@ mod tests {
             #[test]
             fn fail_wildcard_empty_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 2,
+                    part: Part::Verb,
+                    input: "*::".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*::").unwrap_err();
@@ -479,7 +633,10 @@ mod tests {
             #[test]
             fn fail_wildcard_empty_wildcard() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 2,
+                    part: Part::Verb,
+                    input: "*::*".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*::*").unwrap_err();
@@ -511,7 +668,10 @@ mod tests {
             #[test]
             fn fail_wildcard_wildcard_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 4,
+                    part: Part::Resource,
+                    input: "*:*:".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*:*:").unwrap_err();
@@ -540,7 +700,10 @@ mod tests {
             #[test]
             fn fail_wildcard_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 2,
+                    part: Part::Verb,
+                    input: "*:".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*:").unwrap_err();
@@ -551,7 +714,10 @@ mod tests {
             #[test]
             fn fail_wildcard_token() {
                 let expected = ElementParseError {
-                    token: "token".to_string(),
+                    kind: ParseErrorKind::MissingSeparator,
+                    offset: 7,
+                    part: Part::Verb,
+                    input: "*:token".to_string(),
                 };
 
                 let actual = ActionDocument::parse("*:token").unwrap_err();
@@ -561,6 +727,47 @@ mod tests {
         }
     }
 
+    mod escaped {
+        use super::*;
+
+        #[test]
+        fn pass_literal_wildcard() {
+            let document = ActionDocument::parse(r"scope:verb:\*").unwrap();
+
+            assert!(document.is_match(&ScopedAction::parse(r"scope:verb:\*").unwrap()));
+            assert!(!document.is_match(&ScopedAction::parse("scope:verb:resource").unwrap()));
+            assert_eq!(document.to_string(), r"scope:verb:\*");
+        }
+
+        #[test]
+        fn pass_quoted() {
+            let document = ActionDocument::parse(r#""my scope":verb:*"#).unwrap();
+
+            assert!(document.is_match(&ScopedAction::parse(r#""my scope":verb:x"#).unwrap()));
+            assert_eq!(document.to_string(), r#""my scope":verb:*"#);
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            for value in &[
+                "*",
+                "scope:verb:resource",
+                "scope:verb:*",
+                "scope:*:resource",
+                "scope:*",
+                "*:*",
+            ] {
+                let actual = ActionDocument::parse(value).unwrap().to_string();
+
+                assert_eq!(&actual, value);
+            }
+        }
+    }
+
     mod is_match {
         use super::*;
 
@@ -577,7 +784,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
         }
 
@@ -592,7 +799,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -603,7 +810,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -614,7 +821,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -625,7 +832,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             fn document() -> ActionDocument {
@@ -652,7 +859,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -663,7 +870,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -674,7 +881,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -685,7 +892,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
@@ -710,7 +917,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -721,7 +928,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -732,7 +939,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -743,7 +950,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
@@ -768,7 +975,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -779,7 +986,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -790,7 +997,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -801,7 +1008,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
@@ -823,7 +1030,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -834,7 +1041,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -845,7 +1052,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -856,7 +1063,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
@@ -881,7 +1088,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -892,7 +1099,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -903,7 +1110,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -914,7 +1121,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
@@ -939,7 +1146,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -950,7 +1157,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -961,7 +1168,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -972,7 +1179,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
@@ -997,7 +1204,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -1008,7 +1215,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -1019,7 +1226,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -1030,7 +1237,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             fn document() -> WildcardToken<ScopedActionToken> {
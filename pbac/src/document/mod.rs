@@ -5,6 +5,7 @@ mod wildcard;
 use crate::ElementParseError;
 pub use action::ActionDocument;
 pub use resource::ResourceDocument;
+pub(crate) use wildcard::WildcardToken;
 
 pub trait Element<T>
 where
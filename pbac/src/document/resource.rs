@@ -1,8 +1,10 @@
 use crate::document::wildcard::WildcardToken;
+use crate::parser::Parser;
 use crate::resource::ScopedResource;
-use crate::{Element, ElementParseError};
+use crate::{Element, ElementParseError, Part};
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ResourceDocument {
     scoped_resource: WildcardToken<ScopedResourceToken>,
 }
@@ -14,36 +16,71 @@ impl Element<ScopedResource> for ResourceDocument {
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
         Ok(Self {
-            scoped_resource: WildcardToken::<ScopedResourceToken>::parse(value)?,
+            scoped_resource: WildcardToken::<ScopedResourceToken>::read(&mut Parser::new(value))?,
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl ResourceDocument {
+    /// Returns the scope and segment patterns, where `None` is a wildcard.
+    pub(crate) fn pattern(&self) -> (Option<&str>, Option<Vec<Option<&str>>>) {
+        match &self.scoped_resource {
+            WildcardToken::Wildcard => (None, None),
+            WildcardToken::Value(scoped_resource) => (
+                scoped_resource.scope.value(),
+                match &scoped_resource.resource {
+                    WildcardToken::Wildcard => None,
+                    WildcardToken::Value(resource) => {
+                        Some(resource.segments.iter().map(WildcardToken::value).collect())
+                    }
+                },
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ResourceDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.scoped_resource.fmt(f)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 struct ScopedResourceToken {
     scope: WildcardToken<String>,
-    resource: WildcardToken<String>,
+    resource: WildcardToken<ResourceToken>,
 }
 
 impl Element<ScopedResource> for ScopedResourceToken {
     fn is_match(&self, value: &ScopedResource) -> bool {
-        self.scope.is_match(&value.scope) && self.resource.is_match(&value.resource)
+        self.scope.is_match(&value.scope) && self.resource.is_match(&value.segments)
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value.find(':') {
-            None => Err(ElementParseError {
-                token: value.to_string(),
-            }),
-            Some(position) => {
-                let scope = &value[0..position];
-                let resource = &value[position + 1..value.len()];
-
-                Ok(Self {
-                    scope: WildcardToken::<String>::parse(scope)?,
-                    resource: WildcardToken::<String>::parse(resource)?,
-                })
-            }
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl ScopedResourceToken {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(Self {
+            scope: parser.segment(Part::Scope)?.into(),
+            resource: WildcardToken::<ResourceToken>::read(parser)?,
+        })
+    }
+}
+
+impl fmt::Display for ScopedResourceToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.scope, self.resource)
+    }
+}
+
+impl fmt::Display for WildcardToken<ScopedResourceToken> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildcardToken::Wildcard => f.write_str("*"),
+            WildcardToken::Value(document) => document.fmt(f),
         }
     }
 }
@@ -57,9 +94,95 @@ impl Element<ScopedResource> for WildcardToken<ScopedResourceToken> {
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        Ok(match value {
-            "*" => Self::Wildcard,
-            value => Self::Value(ScopedResourceToken::parse(value)?),
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl WildcardToken<ScopedResourceToken> {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(if parser.wildcard() {
+            Self::Wildcard
+        } else {
+            Self::Value(ScopedResourceToken::read(parser)?)
+        })
+    }
+}
+
+/// A lone `*` matches any number of segments, otherwise every segment is matched in turn.
+#[derive(Clone, Debug, PartialEq)]
+struct ResourceToken {
+    segments: Vec<WildcardToken<String>>,
+}
+
+impl Element<Vec<String>> for ResourceToken {
+    fn is_match(&self, value: &Vec<String>) -> bool {
+        self.segments.len() == value.len()
+            && self
+                .segments
+                .iter()
+                .zip(value)
+                .all(|(document, segment)| document.is_match(segment))
+    }
+
+    fn parse(value: &str) -> Result<Self, ElementParseError> {
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl ResourceToken {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(Self {
+            segments: parser
+                .segments(Part::Resource)?
+                .into_iter()
+                .map(WildcardToken::from)
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for ResourceToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.segments.iter().enumerate() {
+            if index > 0 {
+                f.write_str(":")?;
+            }
+
+            segment.fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for WildcardToken<ResourceToken> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildcardToken::Wildcard => f.write_str("*"),
+            WildcardToken::Value(document) => document.fmt(f),
+        }
+    }
+}
+
+impl Element<Vec<String>> for WildcardToken<ResourceToken> {
+    fn is_match(&self, value: &Vec<String>) -> bool {
+        match self {
+            WildcardToken::Wildcard => true,
+            WildcardToken::Value(document) => document.is_match(value),
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, ElementParseError> {
+        Self::read(&mut Parser::new(value))
+    }
+}
+
+impl WildcardToken<ResourceToken> {
+    fn read(parser: &mut Parser) -> Result<Self, ElementParseError> {
+        Ok(if parser.wildcard() {
+            Self::Wildcard
+        } else {
+            Self::Value(ResourceToken::read(parser)?)
         })
     }
 }
@@ -77,6 +200,7 @@ mod tests {
      *      *:*
      */
     use super::*;
+    use crate::ParseErrorKind;
 
     mod parse {
         use super::*;
@@ -98,7 +222,10 @@ mod tests {
             #[test]
             fn fail_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: "".to_string(),
                 };
 
                 let actual = ResourceDocument::parse("").unwrap_err();
@@ -109,7 +236,10 @@ mod tests {
             #[test]
             fn fail_token() {
                 let expected = ElementParseError {
-                    token: "token".to_string(),
+                    kind: ParseErrorKind::MissingSeparator,
+                    offset: 5,
+                    part: Part::Scope,
+                    input: "token".to_string(),
                 };
 
                 let actual = ResourceDocument::parse("token").unwrap_err();
@@ -127,7 +257,9 @@ mod tests {
                     scoped_resource: WildcardToken::<ScopedResourceToken>::Value(
                         ScopedResourceToken {
                             scope: WildcardToken::Value("scope".to_string()),
-                            resource: WildcardToken::Value("resource".to_string()),
+                            resource: WildcardToken::Value(ResourceToken {
+                                segments: vec![WildcardToken::Value("resource".to_string())],
+                            }),
                         },
                     ),
                 };
@@ -140,7 +272,10 @@ mod tests {
             #[test]
             fn fail_scope_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 6,
+                    part: Part::Resource,
+                    input: "scope:".to_string(),
                 };
 
                 let actual = ResourceDocument::parse("scope:").unwrap_err();
@@ -151,7 +286,10 @@ mod tests {
             #[test]
             fn fail_empty_resource() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":resource".to_string(),
                 };
 
                 let actual = ResourceDocument::parse(":resource").unwrap_err();
@@ -162,7 +300,10 @@ mod tests {
             #[test]
             fn fail_empty_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":".to_string(),
                 };
 
                 let actual = ResourceDocument::parse(":").unwrap_err();
@@ -193,7 +334,10 @@ mod tests {
             #[test]
             fn fail_empty_wildcard() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: ":*".to_string(),
                 };
 
                 let actual = ResourceDocument::parse(":*").unwrap_err();
@@ -211,7 +355,9 @@ mod tests {
                     scoped_resource: WildcardToken::<ScopedResourceToken>::Value(
                         ScopedResourceToken {
                             scope: WildcardToken::Wildcard,
-                            resource: WildcardToken::Value("resource".to_string()),
+                            resource: WildcardToken::Value(ResourceToken {
+                                segments: vec![WildcardToken::Value("resource".to_string())],
+                            }),
                         },
                     ),
                 };
@@ -224,7 +370,10 @@ mod tests {
             #[test]
             fn fail_wildcard_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 2,
+                    part: Part::Resource,
+                    input: "*:".to_string(),
                 };
 
                 let actual = ResourceDocument::parse("*:").unwrap_err();
@@ -254,6 +403,41 @@ mod tests {
         }
     }
 
+    mod escaped {
+        use super::*;
+
+        #[test]
+        fn pass_colon() {
+            let document = ResourceDocument::parse(r#"db:"host:5432""#).unwrap();
+
+            let resource = ScopedResource::parse(r"db:host\:5432").unwrap();
+
+            assert!(document.is_match(&resource));
+            assert_eq!(document.to_string(), r"db:host\:5432");
+        }
+
+        #[test]
+        fn pass_literal_wildcard() {
+            let document = ResourceDocument::parse(r"scope:\*").unwrap();
+
+            assert!(document.is_match(&ScopedResource::parse(r#"scope:"*""#).unwrap()));
+            assert!(!document.is_match(&ScopedResource::parse("scope:x").unwrap()));
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            for value in &["*", "scope:resource", "scope:*", "*:resource", "*:*"] {
+                let actual = ResourceDocument::parse(value).unwrap().to_string();
+
+                assert_eq!(&actual, value);
+            }
+        }
+    }
+
     mod is_match {
         use super::*;
 
@@ -270,7 +454,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
         }
 
@@ -285,7 +469,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -296,7 +480,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -307,14 +491,16 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             fn document() -> ResourceDocument {
                 ResourceDocument {
                     scoped_resource: WildcardToken::Value(ScopedResourceToken {
                         scope: WildcardToken::Value("scope".to_string()),
-                        resource: WildcardToken::Value("resource".to_string()),
+                        resource: WildcardToken::Value(ResourceToken {
+                            segments: vec![WildcardToken::Value("resource".to_string())],
+                        }),
                     }),
                 }
             }
@@ -331,7 +517,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -342,7 +528,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             #[test]
@@ -353,7 +539,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             fn document() -> ResourceDocument {
@@ -377,7 +563,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -388,7 +574,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -399,14 +585,16 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, false);
+                assert!(!result);
             }
 
             fn document() -> ResourceDocument {
                 ResourceDocument {
                     scoped_resource: WildcardToken::Value(ScopedResourceToken {
                         scope: WildcardToken::Wildcard,
-                        resource: WildcardToken::Value("resource".to_string()),
+                        resource: WildcardToken::Value(ResourceToken {
+                            segments: vec![WildcardToken::Value("resource".to_string())],
+                        }),
                     }),
                 }
             }
@@ -423,7 +611,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -434,7 +622,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             #[test]
@@ -445,7 +633,7 @@ mod tests {
 
                 let result = document.is_match(&scoped_action);
 
-                assert_eq!(result, true);
+                assert!(result);
             }
 
             fn document() -> ResourceDocument {
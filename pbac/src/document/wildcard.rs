@@ -1,12 +1,33 @@
 use crate::document::ElementParseError;
-use crate::Element;
+use crate::parser::{escape, Parser};
+use crate::{Element, Part};
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum WildcardToken<T> {
     Wildcard,
     Value(T),
 }
 
+impl fmt::Display for WildcardToken<String> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildcardToken::Wildcard => f.write_str("*"),
+            WildcardToken::Value(value) => escape(f, value),
+        }
+    }
+}
+
+impl WildcardToken<String> {
+    /// Returns the literal value, or `None` for a wildcard.
+    pub(crate) fn value(&self) -> Option<&str> {
+        match self {
+            WildcardToken::Wildcard => None,
+            WildcardToken::Value(value) => Some(value),
+        }
+    }
+}
+
 impl Element<String> for WildcardToken<String> {
     fn is_match(&self, value: &String) -> bool {
         match self {
@@ -16,21 +37,15 @@ impl Element<String> for WildcardToken<String> {
     }
 
     fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value {
-            "*" => Ok(Self::Wildcard),
-            value => match value.len() {
-                0 => Err(ElementParseError {
-                    token: value.to_string(),
-                }),
-                _ => Ok(Self::Value(value.to_string())),
-            },
-        }
+        Ok(Parser::new(value).last(Part::Scope)?.into())
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
+    use crate::ParseErrorKind;
 
     mod parse {
         use super::*;
@@ -63,7 +78,10 @@ mod tests {
             #[test]
             fn fail_empty() {
                 let expected = ElementParseError {
-                    token: "".to_string(),
+                    kind: ParseErrorKind::EmptySegment,
+                    offset: 0,
+                    part: Part::Scope,
+                    input: "".to_string(),
                 };
 
                 let result = WildcardToken::<String>::parse("").unwrap_err();
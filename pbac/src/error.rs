@@ -0,0 +1,169 @@
+use std::error::Error;
+use std::fmt;
+
+/// The ElementParseError describes why an element could not be parsed.
+#[derive(Debug, PartialEq)]
+pub struct ElementParseError {
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+
+    /// The byte offset into the input at which it went wrong.
+    pub offset: usize,
+
+    /// The part of the element being parsed.
+    pub part: Part,
+
+    /// The full input.
+    pub input: String,
+}
+
+/// The ParseErrorKind describes the kind of parse error.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    /// The `:` separating a part from the next was not found.
+    MissingSeparator,
+
+    /// A part is empty.
+    EmptySegment,
+
+    /// A part contains whitespace or a control character.
+    InvalidCharacter,
+
+    /// A `\` is not followed by one of the characters it escapes.
+    InvalidEscape,
+
+    /// A quoted part is missing its closing `"`.
+    UnterminatedQuote,
+}
+
+/// The Part describes a part of an element, such as the scope of `scope:verb:resource`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Part {
+    Scope,
+    Verb,
+    Resource,
+}
+
+impl ElementParseError {
+    /// Renders the error followed by the input, with a caret under the offending position.
+    pub fn diagnostic(&self) -> String {
+        let column = self.input[0..self.offset].chars().count();
+
+        format!("{}\n{}\n{}^", self, self.input, " ".repeat(column))
+    }
+}
+
+impl fmt::Display for ElementParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::MissingSeparator => write!(f, "missing `:` after {}", self.part)?,
+            ParseErrorKind::EmptySegment => write!(f, "empty {}", self.part)?,
+            ParseErrorKind::InvalidCharacter => match self.input[self.offset..].chars().next() {
+                Some(character) => write!(f, "invalid character {:?} in {}", character, self.part)?,
+                None => write!(f, "invalid character in {}", self.part)?,
+            },
+            ParseErrorKind::InvalidEscape => write!(f, "invalid escape in {}", self.part)?,
+            ParseErrorKind::UnterminatedQuote => write!(f, "unterminated quote in {}", self.part)?,
+        }
+
+        write!(f, " at byte {} of {:?}", self.offset, self.input)
+    }
+}
+
+impl Error for ElementParseError {}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Part::Scope => "scope",
+            Part::Verb => "verb",
+            Part::Resource => "resource",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(kind: ParseErrorKind, offset: usize, part: Part, input: &str) -> ElementParseError {
+        ElementParseError {
+            kind,
+            offset,
+            part,
+            input: input.to_string(),
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn pass_missing_separator() {
+            let actual = error(
+                ParseErrorKind::MissingSeparator,
+                11,
+                Part::Verb,
+                "scope:token",
+            );
+
+            assert_eq!(
+                actual.to_string(),
+                "missing `:` after verb at byte 11 of \"scope:token\""
+            );
+        }
+
+        #[test]
+        fn pass_empty_segment() {
+            let actual = error(ParseErrorKind::EmptySegment, 6, Part::Verb, "scope::");
+
+            assert_eq!(actual.to_string(), "empty verb at byte 6 of \"scope::\"");
+        }
+
+        #[test]
+        fn pass_invalid_character() {
+            let actual = error(ParseErrorKind::InvalidCharacter, 3, Part::Scope, "sco pe:*");
+
+            assert_eq!(
+                actual.to_string(),
+                "invalid character ' ' in scope at byte 3 of \"sco pe:*\""
+            );
+        }
+    }
+
+    mod diagnostic {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let actual = error(
+                ParseErrorKind::EmptySegment,
+                6,
+                Part::Verb,
+                "scope::resource",
+            );
+
+            assert_eq!(
+                actual.diagnostic(),
+                "empty verb at byte 6 of \"scope::resource\"\nscope::resource\n      ^"
+            );
+        }
+
+        #[test]
+        fn pass_end_of_input() {
+            let actual = error(ParseErrorKind::MissingSeparator, 5, Part::Scope, "token");
+
+            assert_eq!(
+                actual.diagnostic(),
+                "missing `:` after scope at byte 5 of \"token\"\ntoken\n     ^"
+            );
+        }
+
+        #[test]
+        fn pass_multibyte() {
+            let actual = error(ParseErrorKind::EmptySegment, 3, Part::Verb, "é::");
+
+            assert_eq!(actual.diagnostic().lines().last(), Some("  ^"));
+        }
+    }
+}
@@ -0,0 +1,376 @@
+use crate::parser::Parser;
+use crate::{ActionDocument, Element, ElementParseError, Part};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// The ActionGroups describes named sets of action patterns, such as `docs:@ReadOnly` standing for
+/// `docs:read:*` and `docs:list:*`, that policies reference instead of listing the patterns.
+///
+/// Groups are expanded when the actions of a policy are parsed, so changing what a group means
+/// takes effect for the policies parsed afterwards. A group referencing another group is expanded
+/// through it, so it follows the changes to that group too.
+#[derive(Debug, Default)]
+pub struct ActionGroups {
+    groups: BTreeMap<(String, String), Vec<Member>>,
+}
+
+/// The Member describes a pattern of a group, which is an action pattern or a reference to another
+/// group of the same scope.
+#[derive(Debug)]
+enum Member {
+    Action(ActionDocument),
+    Group(String),
+}
+
+/// The ActionGroupError describes why an action or group could not be parsed.
+#[derive(Debug, PartialEq)]
+pub enum ActionGroupError {
+    /// The action references a group that has not been registered in its scope.
+    UnknownGroup { scope: String, name: String },
+
+    /// The pattern of a group matches actions outside the scope of the group.
+    OutOfScope { scope: String, pattern: String },
+
+    /// The group references itself, directly or through other groups.
+    Cycle { scope: String, name: String },
+
+    /// The action is not a valid action document.
+    Parse(ElementParseError),
+}
+
+impl ActionGroups {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the `name` group of the `scope` as the patterns, replacing any previous group of
+    /// that name. A pattern may itself reference a group registered before, which is kept as a
+    /// reference rather than expanded.
+    ///
+    /// Every pattern must name the scope of the group, so that `docs:@Name` only ever stands for
+    /// actions of `docs`. The group is left unchanged if a pattern is invalid, or if the group
+    /// would reference itself.
+    pub fn register(
+        &mut self,
+        scope: &str,
+        name: &str,
+        patterns: &[&str],
+    ) -> Result<(), ActionGroupError> {
+        let out_of_scope = |pattern: String| ActionGroupError::OutOfScope {
+            scope: scope.to_string(),
+            pattern,
+        };
+
+        let mut members = vec![];
+
+        for pattern in patterns {
+            let member = match group(pattern) {
+                Some((group_scope, _)) if group_scope != scope => {
+                    return Err(out_of_scope(pattern.to_string()))
+                }
+                Some((_, group_name)) => Member::Group(group_name),
+                None => {
+                    let document = ActionDocument::parse(pattern)?;
+
+                    if document.pattern()[0] != Some(scope) {
+                        return Err(out_of_scope(document.to_string()));
+                    }
+
+                    Member::Action(document)
+                }
+            };
+
+            members.push(member);
+        }
+
+        let key = (scope.to_string(), name.to_string());
+        let previous = self.groups.insert(key.clone(), members);
+
+        if let Err(error) = self.expand(scope, name, &mut vec![]) {
+            match previous {
+                Some(previous) => self.groups.insert(key, previous),
+                None => self.groups.remove(&key),
+            };
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Parses the action, expanding it to the patterns of its group if it is of the form
+    /// `scope:@Name`.
+    pub fn parse(&self, value: &str) -> Result<Vec<ActionDocument>, ActionGroupError> {
+        let (scope, name) = match group(value) {
+            None => return Ok(vec![ActionDocument::parse(value)?]),
+            Some(group) => group,
+        };
+
+        self.expand(&scope, &name, &mut vec![])
+    }
+
+    /// Parses the actions of a policy, expanding the groups among them.
+    pub fn parse_actions(&self, values: &[&str]) -> Result<Vec<ActionDocument>, ActionGroupError> {
+        let mut documents = vec![];
+
+        for value in values {
+            documents.extend(self.parse(value)?);
+        }
+
+        Ok(documents)
+    }
+
+    /// Returns the action patterns of the group, expanding the groups it references in turn.
+    /// `expanding` holds the names of the groups being expanded, to detect a group referencing
+    /// itself.
+    fn expand(
+        &self,
+        scope: &str,
+        name: &str,
+        expanding: &mut Vec<String>,
+    ) -> Result<Vec<ActionDocument>, ActionGroupError> {
+        if expanding.iter().any(|expanded| expanded == name) {
+            return Err(ActionGroupError::Cycle {
+                scope: scope.to_string(),
+                name: name.to_string(),
+            });
+        }
+
+        let members = self
+            .groups
+            .get(&(scope.to_string(), name.to_string()))
+            .ok_or_else(|| ActionGroupError::UnknownGroup {
+                scope: scope.to_string(),
+                name: name.to_string(),
+            })?;
+
+        expanding.push(name.to_string());
+
+        let mut documents = vec![];
+
+        for member in members {
+            match member {
+                Member::Action(document) => documents.push(document.clone()),
+                Member::Group(group) => documents.extend(self.expand(scope, group, expanding)?),
+            }
+        }
+
+        expanding.pop();
+
+        Ok(documents)
+    }
+}
+
+impl From<ElementParseError> for ActionGroupError {
+    fn from(error: ElementParseError) -> Self {
+        ActionGroupError::Parse(error)
+    }
+}
+
+impl fmt::Display for ActionGroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionGroupError::UnknownGroup { scope, name } => {
+                write!(f, "unknown action group {}:@{}", scope, name)
+            }
+            ActionGroupError::OutOfScope { scope, pattern } => {
+                write!(f, "action group pattern {} is outside {}", pattern, scope)
+            }
+            ActionGroupError::Cycle { scope, name } => {
+                write!(f, "action group {}:@{} references itself", scope, name)
+            }
+            ActionGroupError::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for ActionGroupError {}
+
+/// Returns the scope and name of the group the value references, or `None` if the value is not of
+/// the form `scope:@Name`.
+fn group(value: &str) -> Option<(String, String)> {
+    let mut parser = Parser::new(value);
+
+    let scope = String::from(parser.segment(Part::Scope).ok()?);
+    let rest = String::from(parser.last(Part::Verb).ok()?);
+
+    rest.strip_prefix('@').map(|name| (scope, name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> ActionGroups {
+        let mut groups = ActionGroups::new();
+        groups
+            .register("docs", "ReadOnly", &["docs:read:*", "docs:list:*"])
+            .unwrap();
+        groups
+            .register("docs", "ReadWrite", &["docs:@ReadOnly", "docs:write:*"])
+            .unwrap();
+        groups
+    }
+
+    fn documents(values: &[&str]) -> Vec<ActionDocument> {
+        values
+            .iter()
+            .map(|value| ActionDocument::parse(value).unwrap())
+            .collect()
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn pass_group() {
+            let expected = documents(&["docs:read:*", "docs:list:*"]);
+
+            let actual = groups().parse("docs:@ReadOnly").unwrap();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_nested() {
+            let expected = documents(&["docs:read:*", "docs:list:*", "docs:write:*"]);
+
+            let actual = groups().parse("docs:@ReadWrite").unwrap();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_document() {
+            let expected = documents(&["docs:delete:document"]);
+
+            let actual = groups().parse("docs:delete:document").unwrap();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_unknown() {
+            let expected = ActionGroupError::UnknownGroup {
+                scope: "storage".to_string(),
+                name: "ReadOnly".to_string(),
+            };
+
+            let actual = groups().parse("storage:@ReadOnly").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_document() {
+            let actual = groups().parse("docs:read");
+
+            assert!(matches!(actual, Err(ActionGroupError::Parse(_))));
+        }
+    }
+
+    mod register {
+        use super::*;
+
+        #[test]
+        fn pass_replace() {
+            let mut groups = groups();
+            groups
+                .register("docs", "ReadOnly", &["docs:read:*"])
+                .unwrap();
+
+            let actual = groups.parse("docs:@ReadOnly").unwrap();
+
+            assert_eq!(actual, documents(&["docs:read:*"]));
+        }
+
+        #[test]
+        fn fail_out_of_scope() {
+            let mut groups = groups();
+
+            for pattern in &[
+                "*",
+                "*:read:*",
+                "storage:read:*",
+                "storage:*",
+                "storage:@ReadOnly",
+            ] {
+                let expected = ActionGroupError::OutOfScope {
+                    scope: "docs".to_string(),
+                    pattern: pattern.to_string(),
+                };
+
+                let actual = groups.register("docs", "ReadOnly", &[pattern]).unwrap_err();
+
+                assert_eq!(actual, expected);
+            }
+
+            assert_eq!(
+                groups.parse("docs:@ReadOnly").unwrap(),
+                documents(&["docs:read:*", "docs:list:*"])
+            );
+        }
+
+        #[test]
+        fn pass_nested_follows() {
+            let mut groups = groups();
+            groups
+                .register("docs", "ReadOnly", &["docs:read:*"])
+                .unwrap();
+
+            let actual = groups.parse("docs:@ReadWrite").unwrap();
+
+            assert_eq!(actual, documents(&["docs:read:*", "docs:write:*"]));
+        }
+
+        #[test]
+        fn fail_unknown() {
+            let actual = ActionGroups::new().register("docs", "ReadWrite", &["docs:@ReadOnly"]);
+
+            assert!(matches!(actual, Err(ActionGroupError::UnknownGroup { .. })));
+        }
+
+        #[test]
+        fn fail_cycle() {
+            let expected = ActionGroupError::Cycle {
+                scope: "docs".to_string(),
+                name: "ReadOnly".to_string(),
+            };
+
+            let mut groups = groups();
+
+            let actual = groups
+                .register("docs", "ReadOnly", &["docs:@ReadWrite"])
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+            assert_eq!(
+                groups.parse("docs:@ReadWrite").unwrap(),
+                documents(&["docs:read:*", "docs:list:*", "docs:write:*"])
+            );
+        }
+
+        #[test]
+        fn fail_self() {
+            let actual = ActionGroups::new().register("docs", "ReadOnly", &["docs:@ReadOnly"]);
+
+            assert!(matches!(actual, Err(ActionGroupError::Cycle { .. })));
+        }
+    }
+
+    mod parse_actions {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let expected = documents(&["docs:read:*", "docs:list:*", "storage:read:*"]);
+
+            let actual = groups()
+                .parse_actions(&["docs:@ReadOnly", "storage:read:*"])
+                .unwrap();
+
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use crate::authorizer::is_typed;
+use crate::{
+    CombiningAlgorithm, Decision, DenyOverrides, Effect, Element, Policy, ScopedAction,
+    ScopedResource,
+};
+use std::collections::BTreeMap;
+
+/// The ResourceParentResolver describes where resources sit in a hierarchy, such as a table within
+/// a dataset within a project, when their identifiers do not encode the path.
+pub trait ResourceParentResolver {
+    /// Returns the parent of the resource, or `None` for a resource at the top of the hierarchy.
+    fn parent(&self, resource: &ScopedResource) -> Option<ScopedResource>;
+}
+
+/// The InMemoryResourceParents describes the parents of resources held in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryResourceParents {
+    parents: BTreeMap<String, ScopedResource>,
+}
+
+/// The HierarchyPolicy describes a policy evaluated over a resource hierarchy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HierarchyPolicy {
+    pub policy: Policy,
+
+    /// Whether the policy also applies to the descendants of the resources it covers. A policy
+    /// that opts out applies to the resources it covers only.
+    pub inherit: bool,
+}
+
+/// The HierarchyDecision describes the effect of policies evaluated over a resource hierarchy.
+#[derive(Debug, PartialEq)]
+pub struct HierarchyDecision<'a> {
+    pub effect: Effect,
+
+    /// The policies that determined the effect, and where their grants came from.
+    pub matches: Vec<HierarchyMatch<'a>>,
+}
+
+/// The HierarchyMatch describes a policy applying to a request through one of the resources.
+#[derive(Debug, PartialEq)]
+pub struct HierarchyMatch<'a> {
+    pub policy: &'a Policy,
+
+    /// The requested resource, or the nearest of its ancestors, that the policy covers.
+    pub resource: ScopedResource,
+}
+
+impl InMemoryResourceParents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_parent(&mut self, child: &ScopedResource, parent: &ScopedResource) {
+        self.parents.insert(child.to_string(), parent.clone());
+    }
+}
+
+impl ResourceParentResolver for InMemoryResourceParents {
+    fn parent(&self, resource: &ScopedResource) -> Option<ScopedResource> {
+        self.parents.get(&resource.to_string()).cloned()
+    }
+}
+
+/// Evaluates the policies against the action and resources, letting a policy that covers a
+/// resource also apply to its descendants, unless the policy opts out of inheritance.
+pub fn is_authorized_with_hierarchy<'a>(
+    resolver: &dyn ResourceParentResolver,
+    policies: &'a [HierarchyPolicy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> HierarchyDecision<'a> {
+    let lineages = scoped_resources
+        .iter()
+        .map(|scoped_resource| lineage(resolver, scoped_resource))
+        .collect::<Vec<_>>();
+    let typed = is_typed(scoped_action, scoped_resources);
+
+    let covered = |hierarchy_policy: &HierarchyPolicy| {
+        let policy = &hierarchy_policy.policy;
+        let action_match = policy
+            .actions
+            .iter()
+            .any(|document| document.is_match(scoped_action));
+
+        if !typed || !action_match {
+            return None;
+        }
+
+        lineages.iter().find_map(|lineage| {
+            let depth = if hierarchy_policy.inherit {
+                lineage.len()
+            } else {
+                1
+            };
+
+            lineage[..depth]
+                .iter()
+                .find(|resource| {
+                    policy
+                        .resources
+                        .iter()
+                        .any(|document| document.is_match(resource))
+                })
+                .cloned()
+        })
+    };
+
+    let resources = policies.iter().map(covered).collect::<Vec<_>>();
+
+    let decisions = policies
+        .iter()
+        .zip(&resources)
+        .map(|(hierarchy_policy, resource)| match resource {
+            Some(_) => hierarchy_policy.policy.effect.into(),
+            None => Decision::NotApplicable,
+        })
+        .collect::<Vec<_>>();
+
+    let (decision, indices) = DenyOverrides.combine(&decisions);
+
+    HierarchyDecision {
+        effect: decision.effect(),
+        matches: indices
+            .into_iter()
+            .filter_map(|index| {
+                Some(HierarchyMatch {
+                    policy: &policies[index].policy,
+                    resource: resources[index].clone()?,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Returns the resource followed by its ancestors, nearest first. A cycle of parents is tolerated
+/// and each resource is returned once.
+fn lineage(
+    resolver: &dyn ResourceParentResolver,
+    resource: &ScopedResource,
+) -> Vec<ScopedResource> {
+    let mut lineage = vec![resource.clone()];
+
+    while let Some(parent) = resolver.parent(&lineage[lineage.len() - 1]) {
+        if lineage.contains(&parent) {
+            break;
+        }
+
+        lineage.push(parent);
+    }
+
+    lineage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+
+    fn inherited(effect: Effect, resource: &str) -> HierarchyPolicy {
+        HierarchyPolicy {
+            policy: policy(effect, "data:*", resource),
+            inherit: true,
+        }
+    }
+
+    fn not_inherited(effect: Effect, resource: &str) -> HierarchyPolicy {
+        HierarchyPolicy {
+            inherit: false,
+            ..inherited(effect, resource)
+        }
+    }
+
+    fn resource(value: &str) -> ScopedResource {
+        ScopedResource::parse(value).unwrap()
+    }
+
+    /// The `acme` organization owns the `analytics` project, holding the `events` dataset, holding
+    /// the `clicks` table.
+    fn parents() -> InMemoryResourceParents {
+        let mut parents = InMemoryResourceParents::new();
+        parents.set_parent(
+            &resource("data:table/clicks"),
+            &resource("data:dataset/events"),
+        );
+        parents.set_parent(
+            &resource("data:dataset/events"),
+            &resource("data:project/analytics"),
+        );
+        parents.set_parent(
+            &resource("data:project/analytics"),
+            &resource("data:org/acme"),
+        );
+        parents
+    }
+
+    fn evaluate<'a>(policies: &'a [HierarchyPolicy], value: &str) -> HierarchyDecision<'a> {
+        let action = ScopedAction::parse("data:read:table").unwrap();
+
+        is_authorized_with_hierarchy(&parents(), policies, &action, &[resource(value)])
+    }
+
+    mod is_authorized_with_hierarchy {
+        use super::*;
+
+        #[test]
+        fn pass_ancestor() {
+            let policies = vec![inherited(Effect::Allow, "data:project/analytics")];
+
+            let actual = evaluate(&policies, "data:table/clicks");
+
+            assert_eq!(
+                actual,
+                HierarchyDecision {
+                    effect: Effect::Allow,
+                    matches: vec![HierarchyMatch {
+                        policy: &policies[0].policy,
+                        resource: resource("data:project/analytics"),
+                    }],
+                }
+            );
+        }
+
+        #[test]
+        fn pass_nearest() {
+            let policies = vec![inherited(Effect::Allow, "data:*")];
+
+            let actual = evaluate(&policies, "data:table/clicks");
+
+            assert_eq!(actual.matches[0].resource, resource("data:table/clicks"));
+        }
+
+        #[test]
+        fn fail_opt_out() {
+            let policies = vec![not_inherited(Effect::Allow, "data:project/analytics")];
+
+            let actual = evaluate(&policies, "data:table/clicks");
+
+            assert_eq!(
+                actual,
+                HierarchyDecision {
+                    effect: Effect::Deny,
+                    matches: vec![],
+                }
+            );
+        }
+
+        #[test]
+        fn pass_opt_out_sibling() {
+            let policies = vec![
+                not_inherited(Effect::Allow, "data:project/analytics"),
+                inherited(Effect::Allow, "data:dataset/events"),
+            ];
+
+            let actual = evaluate(&policies, "data:table/clicks");
+
+            assert_eq!(
+                actual,
+                HierarchyDecision {
+                    effect: Effect::Allow,
+                    matches: vec![HierarchyMatch {
+                        policy: &policies[1].policy,
+                        resource: resource("data:dataset/events"),
+                    }],
+                }
+            );
+        }
+
+        #[test]
+        fn pass_opt_out_deny() {
+            let policies = vec![
+                inherited(Effect::Allow, "data:table/clicks"),
+                not_inherited(Effect::Deny, "data:org/acme"),
+            ];
+
+            let actual = evaluate(&policies, "data:table/clicks");
+
+            assert_eq!(
+                actual,
+                HierarchyDecision {
+                    effect: Effect::Allow,
+                    matches: vec![HierarchyMatch {
+                        policy: &policies[0].policy,
+                        resource: resource("data:table/clicks"),
+                    }],
+                }
+            );
+        }
+
+        #[test]
+        fn fail_ancestor_deny() {
+            let policies = vec![
+                inherited(Effect::Allow, "data:table/clicks"),
+                inherited(Effect::Deny, "data:org/acme"),
+            ];
+
+            let actual = evaluate(&policies, "data:table/clicks");
+
+            assert_eq!(
+                actual,
+                HierarchyDecision {
+                    effect: Effect::Deny,
+                    matches: vec![HierarchyMatch {
+                        policy: &policies[1].policy,
+                        resource: resource("data:org/acme"),
+                    }],
+                }
+            );
+        }
+
+        #[test]
+        fn fail_descendant() {
+            let policies = vec![inherited(Effect::Allow, "data:table/clicks")];
+
+            let actual = evaluate(&policies, "data:project/analytics");
+
+            assert_eq!(actual.effect, Effect::Deny);
+        }
+
+        #[test]
+        fn pass_cycle() {
+            let mut parents = parents();
+            parents.set_parent(&resource("data:org/acme"), &resource("data:table/clicks"));
+
+            let policies = vec![inherited(Effect::Allow, "data:other")];
+            let action = ScopedAction::parse("data:read:table").unwrap();
+
+            let actual = is_authorized_with_hierarchy(
+                &parents,
+                &policies,
+                &action,
+                &[resource("data:table/clicks")],
+            );
+
+            assert_eq!(actual.effect, Effect::Deny);
+        }
+    }
+}
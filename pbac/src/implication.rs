@@ -0,0 +1,396 @@
+use crate::authorizer::{combine, is_typed};
+use crate::{DenyOverrides, Effect, Element, Policy, ScopedAction, ScopedResource};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+
+/// The ActionImplications describes, per scope, the verbs that a granted verb implies, such as
+/// `admin` implying `write` and `write` implying `read`.
+#[derive(Debug, Default)]
+pub struct ActionImplications {
+    verbs: BTreeMap<(String, String), BTreeSet<String>>,
+}
+
+/// The ImplicationError describes why implications could not be registered or loaded.
+#[derive(Debug, PartialEq)]
+pub enum ImplicationError {
+    /// A line of the implications file is not of the form `scope:verb => verb, verb`.
+    InvalidLine { line: usize, value: String },
+
+    /// The implication would make a verb imply itself, through the verbs of the cycle.
+    Cycle { scope: String, verbs: Vec<String> },
+}
+
+/// The ImpliedMatch describes a policy applying to a request through an implied verb.
+#[derive(Debug, PartialEq)]
+pub struct ImpliedMatch<'a> {
+    pub policy: &'a Policy,
+
+    /// The verbs from the one the policy grants down to the requested one.
+    pub chain: Vec<String>,
+}
+
+impl ActionImplications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers that granting the `verb` within the `scope` also grants the `implied` verb. An
+    /// implication that would close a cycle is rejected and leaves the graph unchanged.
+    pub fn imply(
+        &mut self,
+        scope: &str,
+        verb: &str,
+        implied: &str,
+    ) -> Result<(), ImplicationError> {
+        if let Some(chain) = self.chain(scope, implied, verb) {
+            return Err(ImplicationError::Cycle {
+                scope: scope.to_string(),
+                verbs: std::iter::once(verb.to_string()).chain(chain).collect(),
+            });
+        }
+
+        self.verbs
+            .entry((scope.to_string(), verb.to_string()))
+            .or_default()
+            .insert(implied.to_string());
+
+        Ok(())
+    }
+
+    /// Parses implications, such as the contents of a file, with one `scope:verb => verb, verb`
+    /// per line. Blank lines and lines starting with `#` are ignored.
+    pub fn parse(value: &str) -> Result<Self, ImplicationError> {
+        let mut implications = Self::new();
+
+        for (index, line) in value.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || ImplicationError::InvalidLine {
+                line: index + 1,
+                value: line.to_string(),
+            };
+
+            let (granted, implied) = line.split_once("=>").ok_or_else(invalid)?;
+            let (scope, verb) = granted.trim().split_once(':').ok_or_else(invalid)?;
+
+            if scope.is_empty() || verb.is_empty() || verb.contains(':') {
+                return Err(invalid());
+            }
+
+            for implied in implied.split(',').map(str::trim) {
+                if implied.is_empty() || implied.contains(':') {
+                    return Err(invalid());
+                }
+
+                implications.imply(scope, verb, implied)?;
+            }
+        }
+
+        Ok(implications)
+    }
+
+    /// Returns the shortest chain of verbs from the `granted` verb to the `requested` verb within
+    /// the `scope`, or `None` if the granted verb does not imply it. A verb implies itself.
+    pub fn chain(&self, scope: &str, granted: &str, requested: &str) -> Option<Vec<String>> {
+        let mut previous = BTreeMap::<&str, &str>::new();
+        let mut queue = VecDeque::from(vec![granted]);
+
+        while let Some(verb) = queue.pop_front() {
+            if verb == requested {
+                let mut chain = vec![verb.to_string()];
+                let mut current = verb;
+
+                while let Some(&before) = previous.get(current) {
+                    chain.push(before.to_string());
+                    current = before;
+                }
+
+                chain.reverse();
+                return Some(chain);
+            }
+
+            let implied = self.verbs.get(&(scope.to_string(), verb.to_string()));
+
+            for next in implied.into_iter().flatten() {
+                if next != granted && !previous.contains_key(next.as_str()) {
+                    previous.insert(next, verb);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for ImplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImplicationError::InvalidLine { line, value } => {
+                write!(f, "line {} is not an implication: {}", line, value)
+            }
+            ImplicationError::Cycle { scope, verbs } => {
+                write!(
+                    f,
+                    "{} verbs imply themselves: {}",
+                    scope,
+                    verbs.join(" => ")
+                )
+            }
+        }
+    }
+}
+
+impl Error for ImplicationError {}
+
+/// Evaluates the policies against the action and resources, letting an allowing policy that grants
+/// a verb also grant the verbs it implies.
+///
+/// Denying policies match the requested verb only, so that denying `admin` does not deny `read`.
+pub fn is_authorized_with_implications<'a>(
+    implications: &ActionImplications,
+    policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> (Effect, Vec<ImpliedMatch<'a>>) {
+    let typed = is_typed(scoped_action, scoped_resources);
+
+    let chain = |policy: &Policy| {
+        let resource_match = policy.resources.iter().any(|document| {
+            scoped_resources
+                .iter()
+                .any(|resource| document.is_match(resource))
+        });
+
+        if !typed || !resource_match {
+            return None;
+        }
+
+        match policy.effect {
+            Effect::Allow => policy
+                .actions
+                .iter()
+                .filter_map(|document| document.implied_match(scoped_action, implications))
+                .min_by_key(|chain| chain.len()),
+            Effect::Deny => policy
+                .actions
+                .iter()
+                .any(|document| document.is_match(scoped_action))
+                .then(|| vec![scoped_action.action.verb.clone()]),
+        }
+    };
+
+    let (decision, policies) = combine(&DenyOverrides, policies, |policy| chain(policy).is_some());
+
+    (
+        decision.effect(),
+        policies
+            .into_iter()
+            .filter_map(|policy| {
+                Some(ImpliedMatch {
+                    policy,
+                    chain: chain(policy)?,
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+
+    fn implications() -> ActionImplications {
+        ActionImplications::parse(
+            "
+            # Higher verbs imply lower ones.
+            docs:admin => write
+            docs:write => read, list
+            ",
+        )
+        .unwrap()
+    }
+
+    fn chain(verbs: &[&str]) -> Option<Vec<String>> {
+        Some(verbs.iter().map(|verb| verb.to_string()).collect())
+    }
+
+    fn evaluate<'a>(policies: &'a [Policy], action: &str) -> (Effect, Vec<ImpliedMatch<'a>>) {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse("docs:readme").unwrap()];
+
+        is_authorized_with_implications(&implications(), policies, &action, &resources)
+    }
+
+    mod imply {
+        use super::*;
+
+        #[test]
+        fn fail_cycle() {
+            let expected = ImplicationError::Cycle {
+                scope: "docs".to_string(),
+                verbs: vec![
+                    "read".to_string(),
+                    "admin".to_string(),
+                    "write".to_string(),
+                    "read".to_string(),
+                ],
+            };
+
+            let mut implications = implications();
+
+            let actual = implications.imply("docs", "read", "admin").unwrap_err();
+
+            assert_eq!(actual, expected);
+            assert_eq!(implications.chain("docs", "read", "admin"), None);
+        }
+
+        #[test]
+        fn fail_self() {
+            let actual = ActionImplications::new().imply("docs", "read", "read");
+
+            assert!(matches!(actual, Err(ImplicationError::Cycle { .. })));
+        }
+
+        #[test]
+        fn pass_other_scope() {
+            let actual = implications().imply("storage", "read", "admin");
+
+            assert_eq!(actual, Ok(()));
+        }
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn fail_line() {
+            let expected = ImplicationError::InvalidLine {
+                line: 2,
+                value: "docs:admin write".to_string(),
+            };
+
+            let actual = ActionImplications::parse("docs:write => read\ndocs:admin write");
+
+            assert_eq!(actual.unwrap_err(), expected);
+        }
+
+        #[test]
+        fn fail_cycle() {
+            let actual = ActionImplications::parse("docs:read => write\ndocs:write => read");
+
+            assert!(matches!(actual, Err(ImplicationError::Cycle { .. })));
+        }
+    }
+
+    mod chain {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let actual = implications().chain("docs", "admin", "read");
+
+            assert_eq!(actual, chain(&["admin", "write", "read"]));
+        }
+
+        #[test]
+        fn pass_itself() {
+            let actual = implications().chain("docs", "read", "read");
+
+            assert_eq!(actual, chain(&["read"]));
+        }
+
+        #[test]
+        fn fail_lower() {
+            let actual = implications().chain("docs", "read", "admin");
+
+            assert_eq!(actual, None);
+        }
+
+        #[test]
+        fn fail_scope() {
+            let actual = implications().chain("storage", "admin", "read");
+
+            assert_eq!(actual, None);
+        }
+    }
+
+    mod is_authorized_with_implications {
+        use super::*;
+
+        #[test]
+        fn pass_implied() {
+            let policies = vec![policy(Effect::Allow, "docs:admin:*", "docs:*")];
+
+            let actual = evaluate(&policies, "docs:read:document");
+
+            assert_eq!(
+                actual,
+                (
+                    Effect::Allow,
+                    vec![ImpliedMatch {
+                        policy: &policies[0],
+                        chain: chain(&["admin", "write", "read"]).unwrap(),
+                    }]
+                )
+            );
+        }
+
+        #[test]
+        fn pass_wildcard() {
+            let policies = vec![policy(Effect::Allow, "docs:*", "docs:*")];
+
+            let actual = evaluate(&policies, "docs:delete:document");
+
+            assert_eq!(actual.1[0].chain, vec!["delete".to_string()]);
+        }
+
+        #[test]
+        fn fail_resource() {
+            let policies = vec![policy(Effect::Allow, "docs:admin:folder", "docs:*")];
+
+            let actual = evaluate(&policies, "docs:read:document");
+
+            assert_eq!(actual, (Effect::Deny, vec![]));
+        }
+
+        #[test]
+        fn fail_deny_not_implied() {
+            let policies = vec![
+                policy(Effect::Allow, "docs:read:*", "docs:*"),
+                policy(Effect::Deny, "docs:admin:*", "docs:*"),
+            ];
+
+            let actual = evaluate(&policies, "docs:read:document");
+
+            assert_eq!(actual.0, Effect::Allow);
+        }
+
+        #[test]
+        fn fail_deny() {
+            let policies = vec![
+                policy(Effect::Allow, "docs:admin:*", "docs:*"),
+                policy(Effect::Deny, "docs:read:*", "docs:*"),
+            ];
+
+            let actual = evaluate(&policies, "docs:read:document");
+
+            assert_eq!(
+                actual,
+                (
+                    Effect::Deny,
+                    vec![ImpliedMatch {
+                        policy: &policies[1],
+                        chain: chain(&["read"]).unwrap(),
+                    }]
+                )
+            );
+        }
+    }
+}
@@ -1,16 +1,78 @@
 mod action;
+mod analysis;
+mod assume;
 mod authorizer;
+mod boundary;
+mod catalog;
+mod combining;
+mod delegation;
 mod document;
+mod error;
+mod group;
+mod hierarchy;
+mod implication;
+mod library;
 mod models;
+mod organization;
+mod parser;
+mod policy_set;
+mod principal;
+mod rbac;
+mod relationship;
 mod resource;
+mod resource_policy;
+mod schema;
+mod session;
+mod template;
+mod tenant;
+#[cfg(test)]
+mod test_utils;
 
 pub use crate::action::ScopedAction;
-pub use crate::authorizer::is_authorized;
+pub use crate::analysis::{
+    check_equivalent, check_subset, diff, Change, Counterexample, Example, PolicyDiff, Region,
+    Segment, Segments, Verdict,
+};
+pub use crate::assume::{AssumableRole, AssumeRoleError, AssumedRole, TrustPolicy};
+pub use crate::authorizer::{check_resource_types, evaluate, is_authorized, ResourceTypeMismatch};
+pub use crate::boundary::{is_authorized_within_boundary, BoundaryDecision};
+pub use crate::catalog::{ActionCatalog, UnknownAction};
+pub use crate::combining::{
+    CombiningAlgorithm, Decision, DenyOverrides, DenyUnlessPermit, FirstApplicable,
+    OnlyOneApplicable, PermitOverrides,
+};
+pub use crate::delegation::{delegate, DelegationError};
 pub use crate::document::{ActionDocument, Element, ResourceDocument};
+pub use crate::error::{ElementParseError, ParseErrorKind, Part};
+pub use crate::group::{ActionGroupError, ActionGroups};
+pub use crate::hierarchy::{
+    is_authorized_with_hierarchy, HierarchyDecision, HierarchyMatch, HierarchyPolicy,
+    InMemoryResourceParents, ResourceParentResolver,
+};
+pub use crate::implication::{
+    is_authorized_with_implications, ActionImplications, ImplicationError, ImpliedMatch,
+};
+pub use crate::library::{LibraryError, PolicyAttachment, PolicyLibrary};
 pub use crate::models::{Effect, Policy, Principal};
+pub use crate::organization::{
+    is_authorized_within_organization, OrganizationDecision, OrganizationalUnit,
+};
+pub use crate::policy_set::{PolicySet, PolicySetChild, PolicySetDecision, Target};
+pub use crate::principal::{is_authorized_as, InMemoryPrincipalResolver, PrincipalResolver};
+pub use crate::rbac::{
+    ConstraintViolation, Grant, Rbac, Role, RoleAssignment, RoleError, RoleSession, SeparationKind,
+    SeparationOfDuty,
+};
+pub use crate::relationship::{
+    is_authorized_with_relationships, InMemoryTupleStore, RelationTuple, Relationships, Rewrite,
+    Subject, TupleStore,
+};
 pub use crate::resource::ScopedResource;
-
-#[derive(Debug, PartialEq)]
-pub struct ElementParseError {
-    pub token: String,
-}
+pub use crate::resource_policy::{is_authorized_with_resource_policies, AttachedResource, Caller};
+pub use crate::schema::{ResourceSchema, SegmentCountMismatch};
+pub use crate::session::{is_authorized_in_session, SessionDecision};
+pub use crate::template::{
+    Parameter, ParameterType, PolicyTemplate, TemplateError, TemplateErrorKind, TemplateInstance,
+    TemplatePolicy,
+};
+pub use crate::tenant::{TenantDecision, TenantPolicies, TenantPrecedence, UnknownTenant};
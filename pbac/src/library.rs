@@ -0,0 +1,343 @@
+use crate::{Policy, Principal, PrincipalResolver};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// The PolicyLibrary describes named, versioned policies, such as `AdministratorAccess`, and the
+/// principals, groups and roles they are attached to by reference.
+///
+/// Attachments are resolved when the effective policies of a principal are requested, so
+/// publishing a new version or detaching a policy takes effect on the next evaluation.
+#[derive(Debug, Default)]
+pub struct PolicyLibrary {
+    /// The versions of each managed policy, oldest first.
+    policies: BTreeMap<String, Vec<Vec<Policy>>>,
+
+    attachments: Vec<PolicyAttachment>,
+}
+
+/// The PolicyAttachment describes a managed policy attached to a principal, group or role.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolicyAttachment {
+    pub principal: Principal,
+
+    /// The name of the managed policy.
+    pub policy: String,
+
+    /// The version attached, starting at 1, or `None` to follow the latest version.
+    pub version: Option<usize>,
+}
+
+/// The LibraryError describes an attachment that references a missing managed policy.
+#[derive(Debug, PartialEq)]
+pub enum LibraryError {
+    UnknownPolicy { policy: String },
+    UnknownVersion { policy: String, version: usize },
+}
+
+impl PolicyLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publishes the policies as the next version of the `name` managed policy, returning the
+    /// version.
+    pub fn publish(&mut self, name: &str, policies: Vec<Policy>) -> usize {
+        let versions = self.policies.entry(name.to_string()).or_default();
+        versions.push(policies);
+        versions.len()
+    }
+
+    /// Returns the policies of the version of the `name` managed policy, or of its latest version.
+    pub fn policy(&self, name: &str, version: Option<usize>) -> Result<&[Policy], LibraryError> {
+        let versions = self
+            .policies
+            .get(name)
+            .ok_or_else(|| LibraryError::UnknownPolicy {
+                policy: name.to_string(),
+            })?;
+
+        let version = version.unwrap_or(versions.len());
+
+        version
+            .checked_sub(1)
+            .and_then(|index| versions.get(index))
+            .map(Vec::as_slice)
+            .ok_or_else(|| LibraryError::UnknownVersion {
+                policy: name.to_string(),
+                version,
+            })
+    }
+
+    /// Attaches the managed policy, replacing any attachment of the same policy to the principal.
+    pub fn attach(&mut self, attachment: PolicyAttachment) -> Result<(), LibraryError> {
+        self.policy(&attachment.policy, attachment.version)?;
+
+        self.detach(&attachment.principal, &attachment.policy);
+        self.attachments.push(attachment);
+
+        Ok(())
+    }
+
+    /// Detaches the `name` managed policy from the principal, returning whether it was attached.
+    pub fn detach(&mut self, principal: &Principal, name: &str) -> bool {
+        let count = self.attachments.len();
+
+        self.attachments
+            .retain(|attachment| &attachment.principal != principal || attachment.policy != name);
+
+        self.attachments.len() != count
+    }
+
+    /// Returns the managed policies attached directly to the principal.
+    pub fn attachments(&self, principal: &Principal) -> Vec<&PolicyAttachment> {
+        self.attachments
+            .iter()
+            .filter(|attachment| &attachment.principal == principal)
+            .collect()
+    }
+
+    /// Returns the policies attached to the principal, or to any group it belongs to, for
+    /// evaluation. A version attached more than once is included once.
+    pub fn effective_policies(
+        &self,
+        resolver: &dyn PrincipalResolver,
+        principal: &Principal,
+    ) -> Vec<Policy> {
+        let mut included = vec![];
+        let mut policies = vec![];
+
+        for principal in resolver.resolve(principal) {
+            for attachment in self.attachments(&principal) {
+                let versions = &self.policies[&attachment.policy];
+                let version = attachment.version.unwrap_or(versions.len());
+
+                if included.contains(&(&attachment.policy, version)) {
+                    continue;
+                }
+
+                included.push((&attachment.policy, version));
+                policies.extend(versions[version - 1].iter().cloned());
+            }
+        }
+
+        policies
+    }
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LibraryError::UnknownPolicy { policy } => {
+                write!(f, "unknown managed policy {}", policy)
+            }
+            LibraryError::UnknownVersion { policy, version } => {
+                write!(
+                    f,
+                    "unknown version {} of managed policy {}",
+                    version, policy
+                )
+            }
+        }
+    }
+}
+
+impl Error for LibraryError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{policy, principal};
+    use crate::{is_authorized, Effect, InMemoryPrincipalResolver, ScopedAction, ScopedResource};
+
+    fn attachment(value: &str, policy: &str, version: Option<usize>) -> PolicyAttachment {
+        PolicyAttachment {
+            principal: principal(value),
+            policy: policy.to_string(),
+            version,
+        }
+    }
+
+    fn resolver() -> InMemoryPrincipalResolver {
+        let mut resolver = InMemoryPrincipalResolver::new();
+        resolver.add_member("group:finance", "user:alice");
+        resolver
+    }
+
+    fn library() -> PolicyLibrary {
+        let mut library = PolicyLibrary::new();
+        library.publish("AdministratorAccess", vec![policy(Effect::Allow, "*", "*")]);
+        library.publish(
+            "BillingReadOnly",
+            vec![policy(Effect::Allow, "billing:read:*", "*")],
+        );
+        library.publish(
+            "BillingReadOnly",
+            vec![policy(Effect::Allow, "billing:get:*", "*")],
+        );
+        library
+    }
+
+    fn is_allowed(policies: &[Policy], action: &str) -> bool {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse("billing:invoice/1").unwrap()];
+
+        is_authorized(policies, &action, &resources).0 == Effect::Allow
+    }
+
+    mod publish {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let actual = library().publish("BillingReadOnly", vec![]);
+
+            assert_eq!(actual, 3);
+        }
+    }
+
+    mod policy {
+        use super::*;
+
+        #[test]
+        fn pass_latest() {
+            let library = library();
+
+            let actual = library.policy("BillingReadOnly", None).unwrap();
+
+            assert_eq!(actual, &[policy(Effect::Allow, "billing:get:*", "*")][..]);
+        }
+
+        #[test]
+        fn fail_version() {
+            let expected = LibraryError::UnknownVersion {
+                policy: "BillingReadOnly".to_string(),
+                version: 0,
+            };
+
+            let actual = library().policy("BillingReadOnly", Some(0)).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod attach {
+        use super::*;
+
+        #[test]
+        fn pass_replace() {
+            let mut library = library();
+            library
+                .attach(attachment("user:alice", "BillingReadOnly", Some(1)))
+                .unwrap();
+            library
+                .attach(attachment("user:alice", "BillingReadOnly", None))
+                .unwrap();
+
+            let actual = library.attachments(&principal("user:alice"));
+
+            assert_eq!(
+                actual,
+                vec![&attachment("user:alice", "BillingReadOnly", None)]
+            );
+        }
+
+        #[test]
+        fn fail_unknown() {
+            let expected = LibraryError::UnknownPolicy {
+                policy: "PowerUserAccess".to_string(),
+            };
+
+            let actual = library()
+                .attach(attachment("user:alice", "PowerUserAccess", None))
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod detach {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let mut library = library();
+            library
+                .attach(attachment("group:finance", "BillingReadOnly", None))
+                .unwrap();
+
+            let detached = library.detach(&principal("group:finance"), "BillingReadOnly");
+            let actual = library.effective_policies(&resolver(), &principal("user:alice"));
+
+            assert!(detached);
+            assert_eq!(actual, vec![]);
+        }
+
+        #[test]
+        fn fail_not_attached() {
+            let actual = library().detach(&principal("user:alice"), "BillingReadOnly");
+
+            assert!(!actual);
+        }
+    }
+
+    mod effective_policies {
+        use super::*;
+
+        #[test]
+        fn pass_group() {
+            let mut library = library();
+            library
+                .attach(attachment("group:finance", "BillingReadOnly", Some(1)))
+                .unwrap();
+
+            let actual = library.effective_policies(&resolver(), &principal("user:alice"));
+
+            assert!(is_allowed(&actual, "billing:read:invoice"));
+            assert!(!is_allowed(&actual, "billing:get:invoice"));
+        }
+
+        #[test]
+        fn pass_latest() {
+            let mut library = library();
+            library
+                .attach(attachment("user:alice", "BillingReadOnly", None))
+                .unwrap();
+            library.publish(
+                "BillingReadOnly",
+                vec![policy(Effect::Allow, "billing:list:*", "*")],
+            );
+
+            let actual = library.effective_policies(&resolver(), &principal("user:alice"));
+
+            assert_eq!(actual, vec![policy(Effect::Allow, "billing:list:*", "*")]);
+        }
+
+        #[test]
+        fn pass_once() {
+            let mut library = library();
+            library
+                .attach(attachment("user:alice", "BillingReadOnly", Some(2)))
+                .unwrap();
+            library
+                .attach(attachment("group:finance", "BillingReadOnly", None))
+                .unwrap();
+
+            let actual = library.effective_policies(&resolver(), &principal("user:alice"));
+
+            assert_eq!(actual, vec![policy(Effect::Allow, "billing:get:*", "*")]);
+        }
+
+        #[test]
+        fn fail_other_principal() {
+            let mut library = library();
+            library
+                .attach(attachment("user:bob", "AdministratorAccess", None))
+                .unwrap();
+
+            let actual = library.effective_policies(&resolver(), &principal("user:alice"));
+
+            assert_eq!(actual, vec![]);
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use crate::document::{ActionDocument, ResourceDocument};
 
 /// The Effect describes the specific effect.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Effect {
     Allow,
     Deny,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Policy {
     /// The Action element describes the specific action or actions that will be allowed or denied.
     pub actions: Vec<ActionDocument>,
@@ -23,5 +23,5 @@ pub struct Policy {
 }
 
 /// The Principal describes the specific principal.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Principal(pub String);
@@ -0,0 +1,250 @@
+use crate::{is_authorized, Effect, Policy, ScopedAction, ScopedResource};
+
+/// The OrganizationalUnit describes a unit of the organization, the guardrails attached to it, and
+/// the units nested within it.
+#[derive(Debug, PartialEq)]
+pub struct OrganizationalUnit {
+    pub name: String,
+
+    /// The policies that everything the principals within the unit do must be allowed by. A unit
+    /// without guardrails restricts nothing.
+    pub guardrails: Vec<Policy>,
+
+    pub children: Vec<OrganizationalUnit>,
+}
+
+/// The OrganizationDecision describes the effect of identity policies evaluated within the
+/// guardrails of an organization.
+#[derive(Debug, PartialEq)]
+pub struct OrganizationDecision<'a> {
+    pub effect: Effect,
+
+    /// The guardrails or identity policies that determined the effect.
+    pub policies: Vec<&'a Policy>,
+
+    /// The name of the unit whose guardrails did not allow the request.
+    pub blocked_by: Option<&'a str>,
+}
+
+/// Evaluates the identity policies of a principal within the root of the organization and the units
+/// along its path, which names the units below the root down to the unit the principal belongs to.
+///
+/// The request is denied unless the guardrails of the root and of every unit along the path allow
+/// it, regardless of what the identity policies grant. An empty path places the principal directly
+/// within the root. A path naming a unit that does not exist is blocked by the first missing unit.
+pub fn is_authorized_within_organization<'a>(
+    root: &'a OrganizationalUnit,
+    path: &[&'a str],
+    identity_policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> OrganizationDecision<'a> {
+    let mut units = vec![root];
+    let mut candidates = &root.children;
+
+    for name in path {
+        match candidates.iter().find(|unit| unit.name == *name) {
+            None => {
+                return OrganizationDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    blocked_by: Some(name),
+                }
+            }
+            Some(unit) => {
+                units.push(unit);
+                candidates = &unit.children;
+            }
+        }
+    }
+
+    for unit in units {
+        if unit.guardrails.is_empty() {
+            continue;
+        }
+
+        let (effect, policies) = is_authorized(&unit.guardrails, scoped_action, scoped_resources);
+
+        if effect == Effect::Deny {
+            return OrganizationDecision {
+                effect,
+                policies,
+                blocked_by: Some(&unit.name),
+            };
+        }
+    }
+
+    let (effect, policies) = is_authorized(identity_policies, scoped_action, scoped_resources);
+
+    OrganizationDecision {
+        effect,
+        policies,
+        blocked_by: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+
+    fn unit(
+        name: &str,
+        guardrails: Vec<Policy>,
+        children: Vec<OrganizationalUnit>,
+    ) -> OrganizationalUnit {
+        OrganizationalUnit {
+            name: name.to_string(),
+            guardrails,
+            children,
+        }
+    }
+
+    /// A root denying deletes, with an engineering unit that may only use `scope` and a sandbox
+    /// unit without guardrails.
+    fn organization() -> OrganizationalUnit {
+        unit(
+            "root",
+            vec![
+                policy(Effect::Allow, "*", "*"),
+                policy(Effect::Deny, "*:delete:*", "*"),
+            ],
+            vec![
+                unit(
+                    "engineering",
+                    vec![policy(Effect::Allow, "scope:*", "*")],
+                    vec![unit("payments", vec![], vec![])],
+                ),
+                unit("sandbox", vec![], vec![]),
+            ],
+        )
+    }
+
+    fn evaluate<'a>(
+        root: &'a OrganizationalUnit,
+        path: &[&'a str],
+        identity_policies: &'a [Policy],
+        action: &str,
+    ) -> OrganizationDecision<'a> {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse("scope:resource").unwrap()];
+
+        is_authorized_within_organization(root, path, identity_policies, &action, &resources)
+    }
+
+    mod is_authorized_within_organization {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let root = organization();
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(
+                &root,
+                &["engineering", "payments"],
+                &identity,
+                "scope:read:resource",
+            );
+
+            assert_eq!(
+                actual,
+                OrganizationDecision {
+                    effect: Effect::Allow,
+                    policies: vec![&identity[0]],
+                    blocked_by: None,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_root() {
+            let root = organization();
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(&root, &["sandbox"], &identity, "x:delete:resource");
+
+            assert_eq!(
+                actual,
+                OrganizationDecision {
+                    effect: Effect::Deny,
+                    policies: vec![&root.guardrails[1]],
+                    blocked_by: Some("root"),
+                }
+            );
+        }
+
+        #[test]
+        fn fail_unit() {
+            let root = organization();
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(
+                &root,
+                &["engineering", "payments"],
+                &identity,
+                "other:read:resource",
+            );
+
+            assert_eq!(
+                actual,
+                OrganizationDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    blocked_by: Some("engineering"),
+                }
+            );
+        }
+
+        #[test]
+        fn fail_identity() {
+            let root = organization();
+            let identity = vec![];
+
+            let actual = evaluate(&root, &["sandbox"], &identity, "other:read:resource");
+
+            assert_eq!(
+                actual,
+                OrganizationDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    blocked_by: None,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_empty_path() {
+            let root = organization();
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(&root, &[], &identity, "scope:delete:resource");
+
+            assert_eq!(
+                actual,
+                OrganizationDecision {
+                    effect: Effect::Deny,
+                    policies: vec![&root.guardrails[1]],
+                    blocked_by: Some("root"),
+                }
+            );
+        }
+
+        #[test]
+        fn fail_missing_unit() {
+            let root = organization();
+            let identity = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(&root, &["finance"], &identity, "scope:read:resource");
+
+            assert_eq!(
+                actual,
+                OrganizationDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    blocked_by: Some("finance"),
+                }
+            );
+        }
+    }
+}
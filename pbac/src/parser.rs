@@ -0,0 +1,469 @@
+use crate::document::WildcardToken;
+use crate::{ElementParseError, ParseErrorKind, Part};
+use std::fmt;
+
+/// The characters that must be escaped with a `\` to be taken literally.
+pub(crate) const ESCAPED: [char; 4] = ['\\', ':', '*', '"'];
+
+/// The Parser reads the `:` separated parts of an element, tracking their offsets into the input.
+///
+/// Within a part, `\:`, `\*`, `\"` and `\\` stand for the literal character. A part may instead be
+/// quoted, as in `"host:port"`, in which case only `\"` and `\\` are escapes, whitespace is
+/// allowed, and a quoted `"*"` is a literal asterisk rather than a wildcard. Control characters
+/// are only allowed escaped by their hexadecimal code point, as in `\u{9}`, quoted or not.
+pub(crate) struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+/// The Until describes where the part being read ends.
+#[derive(Clone, Copy, PartialEq)]
+enum Until {
+    /// At the next separator, which must be present.
+    Separator,
+
+    /// At the next separator, or the end of the input.
+    SeparatorOrEnd,
+
+    /// At the end of the input, treating any `:` as part of the value.
+    End,
+}
+
+/// The Token describes a part after its quotes and escapes have been removed.
+pub(crate) struct Token {
+    value: String,
+    wildcard: bool,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self { input, position: 0 }
+    }
+
+    /// Consumes the rest of the input if it is a lone wildcard.
+    pub(crate) fn wildcard(&mut self) -> bool {
+        let is_wildcard = &self.input[self.position..] == "*";
+
+        if is_wildcard {
+            self.position = self.input.len();
+        }
+
+        is_wildcard
+    }
+
+    /// Consumes the part up to the next separator, along with the separator.
+    pub(crate) fn segment(&mut self, part: Part) -> Result<Token, ElementParseError> {
+        Ok(self.read(part, Until::Separator)?.0)
+    }
+
+    /// Consumes the rest of the input as the last part, in which `:` needs no escaping.
+    pub(crate) fn last(&mut self, part: Part) -> Result<Token, ElementParseError> {
+        Ok(self.read(part, Until::End)?.0)
+    }
+
+    /// Consumes the rest of the input as one or more `:` separated parts.
+    pub(crate) fn segments(&mut self, part: Part) -> Result<Vec<Token>, ElementParseError> {
+        let mut tokens = vec![];
+
+        loop {
+            let (token, separated) = self.read(part, Until::SeparatorOrEnd)?;
+            tokens.push(token);
+
+            if !separated {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    /// Reads a part, returning it along with whether a separator followed it.
+    fn read(&mut self, part: Part, until: Until) -> Result<(Token, bool), ElementParseError> {
+        let start = self.position;
+        let rest = &self.input[start..];
+        let quoted = rest.starts_with('"');
+
+        let mut value = String::new();
+        let mut open = quoted;
+        let mut end = None;
+        let mut characters = rest.char_indices().skip(quoted as usize).peekable();
+
+        while let Some((index, character)) = characters.next() {
+            match character {
+                '\\' => match characters.next() {
+                    Some((_, escaped)) if ESCAPED.contains(&escaped) => value.push(escaped),
+                    Some((_, 'u')) => match unicode(&mut characters) {
+                        Some(character) => value.push(character),
+                        None => {
+                            return Err(self.error(
+                                ParseErrorKind::InvalidEscape,
+                                start + index,
+                                part,
+                            ))
+                        }
+                    },
+                    _ => {
+                        return Err(self.error(ParseErrorKind::InvalidEscape, start + index, part))
+                    }
+                },
+                '"' if open => {
+                    open = false;
+
+                    match characters.peek() {
+                        None => {}
+                        Some((_, ':')) if until != Until::End => {}
+                        Some((index, _)) => {
+                            return Err(self.error(
+                                ParseErrorKind::InvalidCharacter,
+                                start + index,
+                                part,
+                            ))
+                        }
+                    }
+                }
+                ':' if !open && until != Until::End => {
+                    end = Some(index);
+                    break;
+                }
+                character if character.is_control() || (character.is_whitespace() && !open) => {
+                    return Err(self.error(ParseErrorKind::InvalidCharacter, start + index, part));
+                }
+                character => value.push(character),
+            }
+        }
+
+        if open {
+            return Err(self.error(ParseErrorKind::UnterminatedQuote, start, part));
+        }
+
+        if value.is_empty() {
+            return Err(self.error(ParseErrorKind::EmptySegment, start, part));
+        }
+
+        let raw = match end {
+            None if until == Until::Separator => {
+                return Err(self.error(ParseErrorKind::MissingSeparator, self.input.len(), part))
+            }
+            None => {
+                self.position = self.input.len();
+                rest
+            }
+            Some(end) => {
+                self.position = start + end + 1;
+                &rest[0..end]
+            }
+        };
+
+        let token = Token {
+            wildcard: raw == "*",
+            value,
+        };
+
+        Ok((token, end.is_some()))
+    }
+
+    fn error(&self, kind: ParseErrorKind, offset: usize, part: Part) -> ElementParseError {
+        ElementParseError {
+            kind,
+            offset,
+            part,
+            input: self.input.to_string(),
+        }
+    }
+}
+
+/// Reads the `{code}` of a `\u{code}` escape, returning the character of the hexadecimal code
+/// point, or `None` if it is not one.
+fn unicode(characters: &mut impl Iterator<Item = (usize, char)>) -> Option<char> {
+    if characters.next()?.1 != '{' {
+        return None;
+    }
+
+    let mut digits = String::new();
+
+    loop {
+        match characters.next()?.1 {
+            '}' => break,
+            digit if digit.is_ascii_hexdigit() && digits.len() < 6 => digits.push(digit),
+            _ => return None,
+        }
+    }
+
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+}
+
+impl From<Token> for String {
+    fn from(token: Token) -> Self {
+        token.value
+    }
+}
+
+impl From<Token> for WildcardToken<String> {
+    fn from(token: Token) -> Self {
+        if token.wildcard {
+            WildcardToken::Wildcard
+        } else {
+            WildcardToken::Value(token.value)
+        }
+    }
+}
+
+/// Writes the value so that the parser reads it back as the same literal.
+pub(crate) fn escape(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    let quoted = value
+        .chars()
+        .any(|character| character.is_whitespace() && !character.is_control());
+
+    if quoted {
+        f.write_str("\"")?;
+    }
+
+    for character in value.chars() {
+        if character.is_control() {
+            write!(f, "\\u{{{:x}}}", character as u32)?;
+            continue;
+        }
+
+        let escaped = if quoted {
+            character == '\\' || character == '"'
+        } else {
+            ESCAPED.contains(&character)
+        };
+
+        if escaped {
+            f.write_str("\\")?;
+        }
+
+        write!(f, "{}", character)?;
+    }
+
+    if quoted {
+        f.write_str("\"")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Result<(String, bool, String), ElementParseError> {
+        let mut parser = Parser::new(value);
+
+        let first = parser.segment(Part::Scope)?;
+        let last = parser.last(Part::Resource)?;
+
+        Ok((first.value, first.wildcard, last.value))
+    }
+
+    fn error(kind: ParseErrorKind, offset: usize, part: Part, input: &str) -> ElementParseError {
+        ElementParseError {
+            kind,
+            offset,
+            part,
+            input: input.to_string(),
+        }
+    }
+
+    mod read {
+        use super::*;
+
+        #[test]
+        fn pass_escaped() {
+            let actual = parse(r"host\:port:a\*b\\c").unwrap();
+
+            assert_eq!(
+                actual,
+                ("host:port".to_string(), false, r"a*b\c".to_string())
+            );
+        }
+
+        #[test]
+        fn pass_escaped_wildcard() {
+            let actual = parse(r"\*:*").unwrap();
+
+            assert_eq!(actual, ("*".to_string(), false, "*".to_string()));
+        }
+
+        #[test]
+        fn pass_wildcard() {
+            let actual = parse("*:*").unwrap();
+
+            assert_eq!(actual, ("*".to_string(), true, "*".to_string()));
+        }
+
+        #[test]
+        fn pass_quoted() {
+            let actual = parse(r#""urn:a b*":"say \"hi\"""#).unwrap();
+
+            assert_eq!(
+                actual,
+                ("urn:a b*".to_string(), false, r#"say "hi""#.to_string())
+            );
+        }
+
+        #[test]
+        fn pass_quoted_wildcard() {
+            let actual = parse(r#""*":x"#).unwrap();
+
+            assert_eq!(actual, ("*".to_string(), false, "x".to_string()));
+        }
+
+        #[test]
+        fn pass_segments() {
+            let mut parser = Parser::new(r#"a:"b:c":*"#);
+
+            let actual = parser
+                .segments(Part::Resource)
+                .unwrap()
+                .into_iter()
+                .map(|token| (token.value, token.wildcard))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                actual,
+                vec![
+                    ("a".to_string(), false),
+                    ("b:c".to_string(), false),
+                    ("*".to_string(), true),
+                ]
+            );
+        }
+
+        #[test]
+        fn fail_segments_empty() {
+            let expected = error(ParseErrorKind::EmptySegment, 2, Part::Resource, "a:");
+
+            let actual = Parser::new("a:").segments(Part::Resource).err().unwrap();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_last_colon() {
+            let actual = parse("scope:a:b").unwrap();
+
+            assert_eq!(actual, ("scope".to_string(), false, "a:b".to_string()));
+        }
+
+        #[test]
+        fn fail_invalid_escape() {
+            let expected = error(ParseErrorKind::InvalidEscape, 1, Part::Scope, r"a\b:c");
+
+            let actual = parse(r"a\b:c").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_escaped_control() {
+            let actual = parse(r#"a\u{9}b:"c\u{1b}d""#).unwrap();
+
+            assert_eq!(actual, ("a\tb".to_string(), false, "c\u{1b}d".to_string()));
+        }
+
+        #[test]
+        fn fail_invalid_code_point() {
+            let expected = error(
+                ParseErrorKind::InvalidEscape,
+                2,
+                Part::Resource,
+                r"a:\u{d800}",
+            );
+
+            let actual = parse(r"a:\u{d800}").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_control() {
+            let expected = error(
+                ParseErrorKind::InvalidCharacter,
+                3,
+                Part::Resource,
+                "a:\"\t\"",
+            );
+
+            let actual = parse("a:\"\t\"").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_trailing_escape() {
+            let expected = error(ParseErrorKind::InvalidEscape, 2, Part::Resource, r"a:\");
+
+            let actual = parse(r"a:\").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_unterminated_quote() {
+            let expected = error(ParseErrorKind::UnterminatedQuote, 0, Part::Scope, r#""a:b"#);
+
+            let actual = parse(r#""a:b"#).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_after_quote() {
+            let expected = error(
+                ParseErrorKind::InvalidCharacter,
+                3,
+                Part::Scope,
+                r#""a"b:c"#,
+            );
+
+            let actual = parse(r#""a"b:c"#).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_empty_quote() {
+            let expected = error(ParseErrorKind::EmptySegment, 2, Part::Resource, r#"a:"""#);
+
+            let actual = parse(r#"a:"""#).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod escape {
+        use super::*;
+
+        struct Escaped<'a>(&'a str);
+
+        impl fmt::Display for Escaped<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                escape(f, self.0)
+            }
+        }
+
+        #[test]
+        fn pass() {
+            for (value, expected) in &[
+                ("value", "value"),
+                ("host:port", r"host\:port"),
+                ("*", r"\*"),
+                (r#"a\"b"#, r#"a\\\"b"#),
+                ("a b:c", r#""a b:c""#),
+                (r#"say "hi""#, r#""say \"hi\"""#),
+                ("tab\there", r"tab\u{9}here"),
+                ("a b\n", r#""a b\u{a}""#),
+            ] {
+                let actual = Escaped(value).to_string();
+
+                assert_eq!(&actual, expected);
+
+                let mut parser = Parser::new(&actual);
+
+                assert_eq!(&String::from(parser.last(Part::Scope).unwrap()), value);
+            }
+        }
+    }
+}
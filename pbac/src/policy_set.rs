@@ -0,0 +1,291 @@
+use crate::authorizer::is_applicable;
+use crate::{
+    ActionDocument, CombiningAlgorithm, Decision, Element, Policy, ResourceDocument, ScopedAction,
+    ScopedResource,
+};
+
+/// The PolicySet describes a named node of policies and nested sets, whose decisions are combined
+/// with the set's algorithm.
+#[derive(Debug)]
+pub struct PolicySet {
+    /// The name identifying the set in the path of a decision.
+    pub name: String,
+
+    /// The algorithm combining the decisions of the children, in order.
+    pub algorithm: Box<dyn CombiningAlgorithm>,
+
+    /// The requests the set applies to, or every request when absent.
+    pub target: Option<Target>,
+
+    /// The policies and sets of the set.
+    pub children: Vec<PolicySetChild>,
+}
+
+/// The PolicySetChild describes a child of a policy set.
+#[derive(Debug)]
+pub enum PolicySetChild {
+    Policy(Policy),
+    Set(PolicySet),
+}
+
+/// The Target describes the requests a policy set applies to. An empty list of documents matches
+/// any action or resource.
+#[derive(Debug, Default, PartialEq)]
+pub struct Target {
+    pub actions: Vec<ActionDocument>,
+    pub resources: Vec<ResourceDocument>,
+}
+
+/// The PolicySetDecision describes the decision of a policy set and where it was made.
+#[derive(Debug, PartialEq)]
+pub struct PolicySetDecision<'a> {
+    pub decision: Decision,
+
+    /// The names of the sets from the root down to the set that decided, which is the deepest set
+    /// that was alone in determining the decision of its parent.
+    pub path: Vec<&'a str>,
+
+    /// The policies that determined the decision.
+    pub policies: Vec<&'a Policy>,
+}
+
+impl PolicySet {
+    /// Evaluates the set against the action and resources.
+    pub fn evaluate(
+        &self,
+        scoped_action: &ScopedAction,
+        scoped_resources: &[ScopedResource],
+    ) -> PolicySetDecision<'_> {
+        let is_target = self
+            .target
+            .as_ref()
+            .is_none_or(|target| target.is_match(scoped_action, scoped_resources));
+
+        if !is_target {
+            return PolicySetDecision {
+                decision: Decision::NotApplicable,
+                path: vec![&self.name],
+                policies: vec![],
+            };
+        }
+
+        let outcomes = self
+            .children
+            .iter()
+            .map(|child| child.evaluate(scoped_action, scoped_resources))
+            .collect::<Vec<_>>();
+
+        let decisions = outcomes
+            .iter()
+            .map(|outcome| outcome.decision)
+            .collect::<Vec<_>>();
+
+        let (decision, indices) = self.algorithm.combine(&decisions);
+
+        let mut path = vec![self.name.as_str()];
+        if let [index] = indices[..] {
+            path.extend(&outcomes[index].path);
+        }
+
+        let policies = indices
+            .iter()
+            .flat_map(|&index| outcomes[index].policies.iter().copied())
+            .collect();
+
+        PolicySetDecision {
+            decision,
+            path,
+            policies,
+        }
+    }
+}
+
+impl PolicySetChild {
+    fn evaluate(
+        &self,
+        scoped_action: &ScopedAction,
+        scoped_resources: &[ScopedResource],
+    ) -> PolicySetDecision<'_> {
+        match self {
+            PolicySetChild::Set(set) => set.evaluate(scoped_action, scoped_resources),
+            PolicySetChild::Policy(policy) => {
+                if is_applicable(policy, scoped_action, scoped_resources) {
+                    PolicySetDecision {
+                        decision: policy.effect.into(),
+                        path: vec![],
+                        policies: vec![policy],
+                    }
+                } else {
+                    PolicySetDecision {
+                        decision: Decision::NotApplicable,
+                        path: vec![],
+                        policies: vec![],
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Target {
+    /// Returns whether the action, and any of the resources, fall within the target.
+    pub fn is_match(
+        &self,
+        scoped_action: &ScopedAction,
+        scoped_resources: &[ScopedResource],
+    ) -> bool {
+        let action_match = self.actions.is_empty()
+            || self
+                .actions
+                .iter()
+                .any(|document| document.is_match(scoped_action));
+
+        let resource_match = self.resources.is_empty()
+            || self.resources.iter().any(|document| {
+                scoped_resources
+                    .iter()
+                    .any(|scoped_resource| document.is_match(scoped_resource))
+            });
+
+        action_match && resource_match
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+    use crate::{DenyOverrides, Effect, FirstApplicable, PermitOverrides};
+
+    fn child(effect: Effect, action: &str) -> PolicySetChild {
+        PolicySetChild::Policy(policy(effect, action, "*"))
+    }
+
+    /// An organization guardrail denying deletes, over a team set allowing its own scope, over a
+    /// service set whose first applicable policy decides.
+    fn organization() -> PolicySet {
+        PolicySet {
+            name: "organization".to_string(),
+            algorithm: Box::new(DenyOverrides),
+            target: None,
+            children: vec![
+                PolicySetChild::Set(PolicySet {
+                    name: "guardrails".to_string(),
+                    algorithm: Box::new(DenyOverrides),
+                    target: None,
+                    children: vec![child(Effect::Deny, "*:delete:*")],
+                }),
+                PolicySetChild::Set(PolicySet {
+                    name: "team".to_string(),
+                    algorithm: Box::new(PermitOverrides),
+                    target: Some(Target {
+                        actions: vec![ActionDocument::parse("team:*").unwrap()],
+                        resources: vec![],
+                    }),
+                    children: vec![
+                        child(Effect::Allow, "team:*"),
+                        PolicySetChild::Set(PolicySet {
+                            name: "service".to_string(),
+                            algorithm: Box::new(FirstApplicable),
+                            target: None,
+                            children: vec![
+                                child(Effect::Deny, "team:write:secret"),
+                                child(Effect::Allow, "team:write:*"),
+                            ],
+                        }),
+                    ],
+                }),
+            ],
+        }
+    }
+
+    fn evaluate(set: &PolicySet, action: &str) -> (Decision, Vec<String>, usize) {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse("team:resource").unwrap()];
+
+        let actual = set.evaluate(&action, &resources);
+
+        (
+            actual.decision,
+            actual.path.iter().map(|name| name.to_string()).collect(),
+            actual.policies.len(),
+        )
+    }
+
+    fn path(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn pass_guardrail() {
+            let actual = evaluate(&organization(), "team:delete:resource");
+
+            assert_eq!(
+                actual,
+                (Decision::Deny, path(&["organization", "guardrails"]), 1)
+            );
+        }
+
+        #[test]
+        fn pass_nested() {
+            let actual = evaluate(&organization(), "team:read:resource");
+
+            assert_eq!(
+                actual,
+                (Decision::Allow, path(&["organization", "team"]), 1)
+            );
+        }
+
+        #[test]
+        fn pass_overridden() {
+            let actual = evaluate(&organization(), "team:write:secret");
+
+            assert_eq!(
+                actual,
+                (Decision::Allow, path(&["organization", "team"]), 1)
+            );
+        }
+
+        #[test]
+        fn pass_several_deciding() {
+            let mut set = organization();
+            set.children.push(child(Effect::Deny, "*:delete:*"));
+
+            let actual = evaluate(&set, "team:delete:resource");
+
+            assert_eq!(actual, (Decision::Deny, path(&["organization"]), 2));
+        }
+
+        #[test]
+        fn pass_deepest() {
+            let mut set = organization();
+            if let PolicySetChild::Set(team) = &mut set.children[1] {
+                team.algorithm = Box::new(DenyOverrides);
+            }
+
+            let actual = evaluate(&set, "team:write:secret");
+
+            assert_eq!(
+                actual,
+                (
+                    Decision::Deny,
+                    path(&["organization", "team", "service"]),
+                    1
+                )
+            );
+        }
+
+        #[test]
+        fn pass_not_target() {
+            let actual = evaluate(&organization(), "other:read:resource");
+
+            assert_eq!(
+                actual,
+                (Decision::NotApplicable, path(&["organization"]), 0)
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::authorizer::{combine, is_applicable, is_principal};
+use crate::{DenyOverrides, Effect, Policy, Principal, ScopedAction, ScopedResource};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The PrincipalResolver describes a store that knows which groups a principal belongs to.
+pub trait PrincipalResolver {
+    /// Returns the principal followed by every group it belongs to, directly or transitively.
+    fn resolve(&self, principal: &Principal) -> Vec<Principal>;
+}
+
+/// The InMemoryPrincipalResolver describes the members of groups held in memory.
+///
+/// Groups may be members of other groups. A membership cycle, such as two groups containing each
+/// other, is tolerated and each group is resolved once.
+#[derive(Debug, Default)]
+pub struct InMemoryPrincipalResolver {
+    /// The groups each member belongs to directly.
+    memberships: BTreeMap<Principal, BTreeSet<Principal>>,
+}
+
+impl InMemoryPrincipalResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the `member`, a principal or another group, to the `group`.
+    pub fn add_member(&mut self, group: &str, member: &str) {
+        self.memberships
+            .entry(Principal(member.to_string()))
+            .or_default()
+            .insert(Principal(group.to_string()));
+    }
+}
+
+impl PrincipalResolver for InMemoryPrincipalResolver {
+    fn resolve(&self, principal: &Principal) -> Vec<Principal> {
+        let mut resolved = vec![principal.clone()];
+        let mut index = 0;
+
+        while index < resolved.len() {
+            if let Some(groups) = self.memberships.get(&resolved[index]) {
+                for group in groups {
+                    if !resolved.contains(group) {
+                        resolved.push(group.clone());
+                    }
+                }
+            }
+
+            index += 1;
+        }
+
+        resolved
+    }
+}
+
+/// Evaluates the policies that apply to the principal, or to any group it belongs to, against the
+/// action and resources.
+///
+/// A policy without principals applies to every principal.
+pub fn is_authorized_as<'a>(
+    resolver: &dyn PrincipalResolver,
+    principal: &Principal,
+    policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> (Effect, Vec<&'a Policy>) {
+    let principals = resolver.resolve(principal);
+
+    let (decision, policies) = combine(&DenyOverrides, policies, |policy| {
+        is_principal(policy, &principals) && is_applicable(policy, scoped_action, scoped_resources)
+    });
+
+    (decision.effect(), policies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{principal, principal_policy};
+
+    fn resolver() -> InMemoryPrincipalResolver {
+        let mut resolver = InMemoryPrincipalResolver::new();
+        resolver.add_member("group:payments", "user:alice");
+        resolver.add_member("group:engineering", "group:payments");
+        resolver.add_member("group:everyone", "group:engineering");
+        resolver.add_member("group:engineering", "group:everyone");
+        resolver
+    }
+
+    mod resolve {
+        use super::*;
+
+        #[test]
+        fn pass_transitive() {
+            let expected = vec![
+                principal("user:alice"),
+                principal("group:payments"),
+                principal("group:engineering"),
+                principal("group:everyone"),
+            ];
+
+            let actual = resolver().resolve(&principal("user:alice"));
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_cycle() {
+            let expected = vec![principal("group:everyone"), principal("group:engineering")];
+
+            let actual = resolver().resolve(&principal("group:everyone"));
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_unknown() {
+            let actual = resolver().resolve(&principal("user:bob"));
+
+            assert_eq!(actual, vec![principal("user:bob")]);
+        }
+    }
+
+    mod is_authorized_as {
+        use super::*;
+
+        fn evaluate<'a>(policies: &'a [Policy], user: &str) -> (Effect, Vec<&'a Policy>) {
+            let action = ScopedAction::parse("scope:verb:resource").unwrap();
+            let resources = vec![ScopedResource::parse("scope:resource").unwrap()];
+
+            is_authorized_as(&resolver(), &principal(user), policies, &action, &resources)
+        }
+
+        #[test]
+        fn pass_group() {
+            let policies = vec![principal_policy(
+                Effect::Allow,
+                &["group:engineering"],
+                "*",
+                "*",
+            )];
+
+            let actual = evaluate(&policies, "user:alice");
+
+            assert_eq!(actual, (Effect::Allow, vec![&policies[0]]));
+        }
+
+        #[test]
+        fn pass_anyone() {
+            let policies = vec![principal_policy(Effect::Allow, &[], "*", "*")];
+
+            let actual = evaluate(&policies, "user:bob");
+
+            assert_eq!(actual, (Effect::Allow, vec![&policies[0]]));
+        }
+
+        #[test]
+        fn fail_not_member() {
+            let policies = vec![principal_policy(
+                Effect::Allow,
+                &["group:engineering"],
+                "*",
+                "*",
+            )];
+
+            let actual = evaluate(&policies, "user:bob");
+
+            assert_eq!(actual, (Effect::Deny, vec![]));
+        }
+
+        #[test]
+        fn fail_group_deny() {
+            let policies = vec![
+                principal_policy(Effect::Allow, &["user:alice"], "*", "*"),
+                principal_policy(Effect::Deny, &["group:everyone"], "*", "*"),
+            ];
+
+            let actual = evaluate(&policies, "user:alice");
+
+            assert_eq!(actual, (Effect::Deny, vec![&policies[1]]));
+        }
+    }
+}
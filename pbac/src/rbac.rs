@@ -0,0 +1,878 @@
+use crate::authorizer::{combine, is_applicable, is_principal};
+use crate::{
+    ActionDocument, Decision, DenyOverrides, Effect, Policy, Principal, ResourceDocument,
+    ScopedAction, ScopedResource,
+};
+use std::error::Error;
+use std::fmt;
+
+/// The Role describes a named bundle of permissions that can be assigned to principals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Role {
+    pub name: String,
+    pub grants: Vec<Grant>,
+
+    /// The names of the junior roles whose permissions this role inherits, transitively.
+    pub inherits: Vec<String>,
+}
+
+/// The Grant describes the actions a role allows on the resources.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grant {
+    pub actions: Vec<ActionDocument>,
+    pub resources: Vec<ResourceDocument>,
+}
+
+/// The RoleAssignment describes a principal holding a role.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoleAssignment {
+    pub principal: Principal,
+
+    /// The name of the role.
+    pub role: String,
+
+    /// Limits the assignment to the resources under a prefix, such as `docs:team-a`, rather than
+    /// every resource the role grants. A resource is under the prefix when it has the scope of the
+    /// prefix and its segments start with those of the prefix, as `docs:team-a` and
+    /// `docs:team-a:drafts:readme` do, but `docs:team-ab:readme` does not.
+    pub within: Option<ScopedResource>,
+}
+
+/// The SeparationOfDuty describes roles of which a principal may hold, or activate, at most one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeparationOfDuty {
+    pub name: String,
+    pub roles: Vec<String>,
+    pub kind: SeparationKind,
+}
+
+/// The SeparationKind describes when a separation of duty applies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SeparationKind {
+    /// The roles may not be assigned to the same principal.
+    Static,
+
+    /// The roles may be assigned to the same principal, but not activated in the same session.
+    Dynamic,
+}
+
+/// The RoleSession describes the roles a principal has activated. Sessions are only made by
+/// `Rbac::activate`, which checks the dynamic separations of duty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoleSession {
+    principal: Principal,
+    roles: Vec<String>,
+}
+
+/// The ConstraintViolation describes a principal holding, or activating, roles that a separation
+/// of duty keeps apart.
+#[derive(Debug, PartialEq)]
+pub struct ConstraintViolation {
+    pub principal: Principal,
+
+    /// The name of the separation of duty.
+    pub constraint: String,
+
+    /// The roles of the separation of duty that the principal holds, or activated, together.
+    pub roles: Vec<String>,
+}
+
+/// The RoleError describes why a role could not be assigned, or roles could not be activated.
+#[derive(Debug, PartialEq)]
+pub enum RoleError {
+    /// The role has not been added.
+    UnknownRole {
+        role: String,
+    },
+
+    /// The role is not assigned to the principal.
+    Unassigned {
+        principal: Principal,
+        role: String,
+    },
+
+    Violation(ConstraintViolation),
+}
+
+/// The Rbac describes roles, their assignments to principals, and the separations of duty between
+/// them.
+///
+/// Assignments are only made by `Rbac::assign`, which checks the static separations of duty.
+#[derive(Debug, Default)]
+pub struct Rbac {
+    roles: Vec<Role>,
+    assignments: Vec<RoleAssignment>,
+    constraints: Vec<SeparationOfDuty>,
+}
+
+impl RoleSession {
+    pub fn principal(&self) -> &Principal {
+        &self.principal
+    }
+
+    /// The names of the activated roles.
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+}
+
+impl Rbac {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn roles(&self) -> &[Role] {
+        &self.roles
+    }
+
+    pub fn assignments(&self) -> &[RoleAssignment] {
+        &self.assignments
+    }
+
+    pub fn constraints(&self) -> &[SeparationOfDuty] {
+        &self.constraints
+    }
+
+    pub fn add_role(&mut self, role: Role) {
+        self.roles.push(role);
+    }
+
+    pub fn add_constraint(&mut self, constraint: SeparationOfDuty) {
+        self.constraints.push(constraint);
+    }
+
+    /// Assigns the role to the principal, optionally limited to the resources under `within`.
+    ///
+    /// The assignment is rejected if the role has not been added, or if the principal would hold
+    /// roles that a static separation of duty keeps apart, counting the roles each role inherits.
+    pub fn assign(
+        &mut self,
+        principal: &Principal,
+        role: &str,
+        within: Option<ScopedResource>,
+    ) -> Result<(), RoleError> {
+        if !self.roles.iter().any(|known| known.name == role) {
+            return Err(RoleError::UnknownRole {
+                role: role.to_string(),
+            });
+        }
+
+        let mut roles = self.assigned(principal);
+        roles.push(role);
+
+        if let Some(violation) = self
+            .violations(principal, &roles, SeparationKind::Static)
+            .into_iter()
+            .next()
+        {
+            return Err(RoleError::Violation(violation));
+        }
+
+        self.assignments.push(RoleAssignment {
+            principal: principal.clone(),
+            role: role.to_string(),
+            within,
+        });
+
+        Ok(())
+    }
+
+    /// Returns the static separations of duty that the existing assignments violate, such as
+    /// those assigned before a constraint was added.
+    pub fn audit(&self) -> Vec<ConstraintViolation> {
+        let mut principals = self
+            .assignments
+            .iter()
+            .map(|assignment| &assignment.principal)
+            .collect::<Vec<_>>();
+        principals.sort();
+        principals.dedup();
+
+        principals
+            .into_iter()
+            .flat_map(|principal| {
+                self.violations(principal, &self.assigned(principal), SeparationKind::Static)
+            })
+            .collect()
+    }
+
+    /// Activates roles assigned to the principal for a session.
+    ///
+    /// The activation is rejected if the roles are not all assigned, or if a dynamic separation of
+    /// duty keeps any of them apart.
+    pub fn activate(
+        &self,
+        principal: &Principal,
+        roles: &[&str],
+    ) -> Result<RoleSession, RoleError> {
+        let assigned = self.assigned(principal);
+
+        if let Some(role) = roles.iter().find(|role| !assigned.contains(role)) {
+            return Err(RoleError::Unassigned {
+                principal: principal.clone(),
+                role: role.to_string(),
+            });
+        }
+
+        if let Some(violation) = self
+            .violations(principal, roles, SeparationKind::Dynamic)
+            .into_iter()
+            .next()
+        {
+            return Err(RoleError::Violation(violation));
+        }
+
+        Ok(RoleSession {
+            principal: principal.clone(),
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+        })
+    }
+
+    /// Translates the assignments that apply to the resource into policies allowing each principal
+    /// the grants of its roles, including those the roles inherit, for evaluating requests on the
+    /// resource. Assignments of roles that do not exist grant nothing.
+    ///
+    /// An assignment applies to the resource unless it is limited to a prefix the resource is not
+    /// under.
+    pub fn policies(&self, scoped_resource: &ScopedResource) -> Vec<Policy> {
+        self.policies_of(
+            self.assignments
+                .iter()
+                .filter(|assignment| assignment.applies_to(scoped_resource)),
+        )
+    }
+
+    /// Returns whether the roles assigned to the principal allow the action on the resource.
+    pub fn check(
+        &self,
+        principal: &Principal,
+        scoped_action: &ScopedAction,
+        scoped_resource: &ScopedResource,
+    ) -> bool {
+        is_allowed(
+            &self.policies(scoped_resource),
+            principal,
+            scoped_action,
+            scoped_resource,
+        )
+    }
+
+    /// Returns whether the roles activated in the session allow the action on the resource.
+    ///
+    /// Nothing is allowed if a dynamic separation of duty keeps the roles of the session apart,
+    /// such as one added after the session was activated.
+    pub fn check_session(
+        &self,
+        session: &RoleSession,
+        scoped_action: &ScopedAction,
+        scoped_resource: &ScopedResource,
+    ) -> bool {
+        let roles = session.roles.iter().map(String::as_str).collect::<Vec<_>>();
+
+        if !self
+            .violations(&session.principal, &roles, SeparationKind::Dynamic)
+            .is_empty()
+        {
+            return false;
+        }
+
+        let policies = self.policies_of(self.assignments.iter().filter(|assignment| {
+            assignment.principal == session.principal
+                && session.roles.contains(&assignment.role)
+                && assignment.applies_to(scoped_resource)
+        }));
+
+        is_allowed(
+            &policies,
+            &session.principal,
+            scoped_action,
+            scoped_resource,
+        )
+    }
+
+    fn policies_of<'a>(
+        &self,
+        assignments: impl Iterator<Item = &'a RoleAssignment>,
+    ) -> Vec<Policy> {
+        assignments
+            .flat_map(|assignment| {
+                self.inherited(&assignment.role)
+                    .into_iter()
+                    .filter_map(|name| self.roles.iter().find(|role| role.name == name))
+                    .flat_map(|role| &role.grants)
+                    .map(move |grant| policy(assignment, grant))
+            })
+            .collect()
+    }
+
+    fn assigned(&self, principal: &Principal) -> Vec<&str> {
+        self.assignments
+            .iter()
+            .filter(|assignment| assignment.principal == *principal)
+            .map(|assignment| assignment.role.as_str())
+            .collect()
+    }
+
+    /// Returns the role followed by every role it inherits, directly or transitively. A cycle of
+    /// inheritance is tolerated and each role is returned once.
+    fn inherited<'a>(&'a self, name: &'a str) -> Vec<&'a str> {
+        let mut inherited = vec![name];
+        let mut index = 0;
+
+        while index < inherited.len() {
+            if let Some(role) = self.roles.iter().find(|role| role.name == inherited[index]) {
+                for junior in &role.inherits {
+                    if !inherited.contains(&junior.as_str()) {
+                        inherited.push(junior);
+                    }
+                }
+            }
+
+            index += 1;
+        }
+
+        inherited
+    }
+
+    fn violations(
+        &self,
+        principal: &Principal,
+        roles: &[&str],
+        kind: SeparationKind,
+    ) -> Vec<ConstraintViolation> {
+        let held = roles
+            .iter()
+            .flat_map(|role| self.inherited(role))
+            .collect::<Vec<_>>();
+
+        self.constraints
+            .iter()
+            .filter(|constraint| constraint.kind == kind)
+            .filter_map(|constraint| {
+                let roles = constraint
+                    .roles
+                    .iter()
+                    .filter(|role| held.contains(&role.as_str()))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if roles.len() < 2 {
+                    return None;
+                }
+
+                Some(ConstraintViolation {
+                    principal: principal.clone(),
+                    constraint: constraint.name.clone(),
+                    roles,
+                })
+            })
+            .collect()
+    }
+}
+
+impl RoleAssignment {
+    /// Returns whether the assignment applies to the resource, being under its prefix if any.
+    fn applies_to(&self, scoped_resource: &ScopedResource) -> bool {
+        self.within.as_ref().is_none_or(|within| {
+            within.scope == scoped_resource.scope
+                && scoped_resource.segments.starts_with(&within.segments)
+        })
+    }
+}
+
+impl fmt::Display for ConstraintViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} may not hold {} together, as separated by {}",
+            self.principal.0,
+            self.roles.join(", "),
+            self.constraint
+        )
+    }
+}
+
+impl Error for ConstraintViolation {}
+
+impl fmt::Display for RoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoleError::UnknownRole { role } => write!(f, "unknown role {}", role),
+            RoleError::Unassigned { principal, role } => {
+                write!(f, "{} is not assigned {}", principal.0, role)
+            }
+            RoleError::Violation(violation) => violation.fmt(f),
+        }
+    }
+}
+
+impl Error for RoleError {}
+
+/// Returns the policy allowing the grant to the principal of the assignment.
+fn policy(assignment: &RoleAssignment, grant: &Grant) -> Policy {
+    Policy {
+        actions: grant.actions.clone(),
+        effect: Effect::Allow,
+        principals: vec![assignment.principal.clone()],
+        resources: grant.resources.clone(),
+    }
+}
+
+fn is_allowed(
+    policies: &[Policy],
+    principal: &Principal,
+    scoped_action: &ScopedAction,
+    scoped_resource: &ScopedResource,
+) -> bool {
+    let scoped_resources = std::slice::from_ref(scoped_resource);
+
+    let (decision, _) = combine(&DenyOverrides, policies, |policy| {
+        is_principal(policy, std::slice::from_ref(principal))
+            && is_applicable(policy, scoped_action, scoped_resources)
+    });
+
+    decision == Decision::Allow
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::principal;
+    use crate::Element;
+
+    fn grant(action: &str, resource: &str) -> Grant {
+        Grant {
+            actions: vec![ActionDocument::parse(action).unwrap()],
+            resources: vec![ResourceDocument::parse(resource).unwrap()],
+        }
+    }
+
+    fn role(name: &str, grants: Vec<Grant>, inherits: &[&str]) -> Role {
+        Role {
+            name: name.to_string(),
+            grants,
+            inherits: inherits.iter().map(|role| role.to_string()).collect(),
+        }
+    }
+
+    fn constraint(name: &str, roles: &[&str], kind: SeparationKind) -> SeparationOfDuty {
+        SeparationOfDuty {
+            name: name.to_string(),
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            kind,
+        }
+    }
+
+    fn rbac() -> Rbac {
+        let mut rbac = Rbac::new();
+        rbac.add_role(role("reader", vec![grant("docs:read:*", "docs:*")], &[]));
+        rbac.add_role(role(
+            "editor",
+            vec![grant("docs:write:*", "docs:*:*")],
+            &["reader"],
+        ));
+        rbac.assign(&principal("user:alice"), "reader", None)
+            .unwrap();
+        rbac.assign(
+            &principal("user:bob"),
+            "editor",
+            Some(ScopedResource::parse("docs:team-a").unwrap()),
+        )
+        .unwrap();
+        rbac
+    }
+
+    /// Payments that are requested by one principal must be approved by another.
+    fn payments() -> Rbac {
+        let mut rbac = Rbac::new();
+        rbac.add_role(role(
+            "requester",
+            vec![grant("payments:request:*", "*")],
+            &[],
+        ));
+        rbac.add_role(role(
+            "approver",
+            vec![grant("payments:approve:*", "*")],
+            &[],
+        ));
+        rbac.add_role(role("auditor", vec![grant("payments:read:*", "*")], &[]));
+        rbac.add_role(role("manager", vec![], &["requester", "auditor"]));
+        rbac.add_constraint(constraint(
+            "payments",
+            &["requester", "approver"],
+            SeparationKind::Static,
+        ));
+        rbac.add_constraint(constraint(
+            "audit",
+            &["requester", "auditor"],
+            SeparationKind::Dynamic,
+        ));
+        rbac
+    }
+
+    fn check(rbac: &Rbac, user: &str, action: &str, resource: &str) -> bool {
+        rbac.check(
+            &principal(user),
+            &ScopedAction::parse(action).unwrap(),
+            &ScopedResource::parse(resource).unwrap(),
+        )
+    }
+
+    mod policies {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let expected = vec![
+                Policy {
+                    actions: vec![ActionDocument::parse("docs:read:*").unwrap()],
+                    effect: Effect::Allow,
+                    principals: vec![principal("user:alice")],
+                    resources: vec![ResourceDocument::parse("docs:*").unwrap()],
+                },
+                Policy {
+                    actions: vec![ActionDocument::parse("docs:write:*").unwrap()],
+                    effect: Effect::Allow,
+                    principals: vec![principal("user:bob")],
+                    resources: vec![ResourceDocument::parse("docs:*:*").unwrap()],
+                },
+                Policy {
+                    actions: vec![ActionDocument::parse("docs:read:*").unwrap()],
+                    effect: Effect::Allow,
+                    principals: vec![principal("user:bob")],
+                    resources: vec![ResourceDocument::parse("docs:*").unwrap()],
+                },
+            ];
+
+            let actual = rbac().policies(&ScopedResource::parse("docs:team-a:readme").unwrap());
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_outside_prefix() {
+            let expected = vec![Policy {
+                actions: vec![ActionDocument::parse("docs:read:*").unwrap()],
+                effect: Effect::Allow,
+                principals: vec![principal("user:alice")],
+                resources: vec![ResourceDocument::parse("docs:*").unwrap()],
+            }];
+
+            let actual = rbac().policies(&ScopedResource::parse("docs:team-b:readme").unwrap());
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn pass_inheritance_cycle() {
+            let mut rbac = Rbac::new();
+            rbac.add_role(role("a", vec![grant("docs:read:*", "*")], &["b"]));
+            rbac.add_role(role("b", vec![grant("docs:write:*", "*")], &["a"]));
+            rbac.assign(&principal("user:alice"), "a", None).unwrap();
+
+            let actual = rbac.policies(&ScopedResource::parse("docs:readme").unwrap());
+
+            assert_eq!(actual.len(), 2);
+        }
+    }
+
+    mod check {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let rbac = rbac();
+
+            assert!(check(
+                &rbac,
+                "user:alice",
+                "docs:read:document",
+                "docs:readme"
+            ));
+            assert!(check(
+                &rbac,
+                "user:bob",
+                "docs:write:document",
+                "docs:team-a:readme"
+            ));
+        }
+
+        #[test]
+        fn pass_inherited() {
+            let rbac = rbac();
+
+            assert!(check(
+                &rbac,
+                "user:bob",
+                "docs:read:document",
+                "docs:team-a:readme"
+            ));
+        }
+
+        #[test]
+        fn fail_not_granted() {
+            let rbac = rbac();
+
+            assert!(!check(
+                &rbac,
+                "user:alice",
+                "docs:write:document",
+                "docs:readme"
+            ));
+        }
+
+        #[test]
+        fn pass_prefix() {
+            let rbac = rbac();
+
+            assert!(check(
+                &rbac,
+                "user:bob",
+                "docs:read:document",
+                "docs:team-a"
+            ));
+        }
+
+        #[test]
+        fn pass_under_prefix() {
+            let rbac = rbac();
+
+            assert!(check(
+                &rbac,
+                "user:bob",
+                "docs:read:document",
+                "docs:team-a:drafts:readme"
+            ));
+        }
+
+        #[test]
+        fn fail_outside_prefix() {
+            let rbac = rbac();
+
+            assert!(!check(
+                &rbac,
+                "user:bob",
+                "docs:write:document",
+                "docs:team-b:readme"
+            ));
+        }
+
+        #[test]
+        fn fail_prefix_boundary() {
+            let rbac = rbac();
+
+            assert!(!check(
+                &rbac,
+                "user:bob",
+                "docs:write:document",
+                "docs:team-ab:readme"
+            ));
+        }
+
+        #[test]
+        fn fail_prefix_scope() {
+            let rbac = rbac();
+
+            assert!(!check(
+                &rbac,
+                "user:bob",
+                "docs:read:document",
+                "wiki:team-a:readme"
+            ));
+        }
+
+        #[test]
+        fn fail_not_granted_under_prefix() {
+            let rbac = rbac();
+
+            assert!(!check(
+                &rbac,
+                "user:bob",
+                "docs:write:document",
+                "docs:team-a:drafts:readme"
+            ));
+        }
+
+        #[test]
+        fn fail_other_principal() {
+            let rbac = rbac();
+
+            assert!(!check(
+                &rbac,
+                "user:carol",
+                "docs:read:document",
+                "docs:readme"
+            ));
+        }
+    }
+
+    mod assign {
+        use super::*;
+
+        #[test]
+        fn fail_static() {
+            let expected = RoleError::Violation(ConstraintViolation {
+                principal: principal("user:alice"),
+                constraint: "payments".to_string(),
+                roles: vec!["requester".to_string(), "approver".to_string()],
+            });
+
+            let mut rbac = payments();
+            rbac.assign(&principal("user:alice"), "approver", None)
+                .unwrap();
+
+            let actual = rbac
+                .assign(&principal("user:alice"), "manager", None)
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+            assert_eq!(
+                actual.to_string(),
+                "user:alice may not hold requester, approver together, as separated by payments"
+            );
+            assert_eq!(rbac.assignments().len(), 1);
+        }
+
+        #[test]
+        fn fail_unknown_role() {
+            let expected = RoleError::UnknownRole {
+                role: "admin".to_string(),
+            };
+
+            let mut rbac = Rbac::new();
+
+            let actual = rbac
+                .assign(&principal("user:alice"), "admin", None)
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+            assert_eq!(rbac.assignments(), &[]);
+        }
+
+        #[test]
+        fn pass_dynamic() {
+            let mut rbac = payments();
+
+            let actual = rbac.assign(&principal("user:alice"), "manager", None);
+
+            assert_eq!(actual, Ok(()));
+        }
+    }
+
+    mod audit {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let mut rbac = payments();
+            rbac.assign(&principal("user:alice"), "requester", None)
+                .unwrap();
+
+            assert_eq!(rbac.audit(), vec![]);
+        }
+
+        #[test]
+        fn fail_existing() {
+            let mut rbac = payments();
+            rbac.constraints.clear();
+            rbac.assign(&principal("user:bob"), "requester", None)
+                .unwrap();
+            rbac.assign(&principal("user:bob"), "approver", None)
+                .unwrap();
+            rbac.add_constraint(constraint(
+                "payments",
+                &["requester", "approver"],
+                SeparationKind::Static,
+            ));
+
+            let actual = rbac.audit();
+
+            assert_eq!(
+                actual,
+                vec![ConstraintViolation {
+                    principal: principal("user:bob"),
+                    constraint: "payments".to_string(),
+                    roles: vec!["requester".to_string(), "approver".to_string()],
+                }]
+            );
+        }
+    }
+
+    mod activate {
+        use super::*;
+
+        fn manager() -> Rbac {
+            let mut rbac = payments();
+            rbac.assign(&principal("user:alice"), "requester", None)
+                .unwrap();
+            rbac.assign(&principal("user:alice"), "auditor", None)
+                .unwrap();
+            rbac
+        }
+
+        #[test]
+        fn pass() {
+            let rbac = manager();
+
+            let session = rbac
+                .activate(&principal("user:alice"), &["auditor"])
+                .unwrap();
+
+            let action = ScopedAction::parse("payments:read:payment").unwrap();
+            let resource = ScopedResource::parse("payments:payment/1").unwrap();
+            assert!(rbac.check_session(&session, &action, &resource));
+
+            let action = ScopedAction::parse("payments:request:payment").unwrap();
+            assert!(!rbac.check_session(&session, &action, &resource));
+        }
+
+        #[test]
+        fn fail_session_dynamic() {
+            let rbac = manager();
+
+            let session = RoleSession {
+                principal: principal("user:alice"),
+                roles: vec!["requester".to_string(), "auditor".to_string()],
+            };
+
+            let action = ScopedAction::parse("payments:read:payment").unwrap();
+            let resource = ScopedResource::parse("payments:payment/1").unwrap();
+            assert!(!rbac.check_session(&session, &action, &resource));
+        }
+
+        #[test]
+        fn fail_dynamic() {
+            let rbac = manager();
+
+            let actual = rbac
+                .activate(&principal("user:alice"), &["requester", "auditor"])
+                .unwrap_err();
+
+            assert_eq!(
+                actual,
+                RoleError::Violation(ConstraintViolation {
+                    principal: principal("user:alice"),
+                    constraint: "audit".to_string(),
+                    roles: vec!["requester".to_string(), "auditor".to_string()],
+                })
+            );
+        }
+
+        #[test]
+        fn fail_unassigned() {
+            let rbac = manager();
+
+            let actual = rbac
+                .activate(&principal("user:alice"), &["approver"])
+                .unwrap_err();
+
+            assert_eq!(
+                actual,
+                RoleError::Unassigned {
+                    principal: principal("user:alice"),
+                    role: "approver".to_string(),
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use crate::authorizer::{combine, is_applicable};
+use crate::{DenyOverrides, Effect, Policy, Principal, ScopedAction, ScopedResource};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// The RelationTuple describes a subject related to an object, written `object#relation@subject`,
+/// such as `folder:home#editor@user:alice`.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct RelationTuple {
+    pub object: String,
+    pub relation: String,
+    pub subject: Subject,
+}
+
+/// The Subject describes who, or what, an object is related to.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Subject {
+    /// A principal, or an object, such as `user:alice` or `folder:home`.
+    Object(String),
+
+    /// Every subject of the relation of the object, such as `group:engineering#member`.
+    Userset { object: String, relation: String },
+}
+
+/// The Rewrite describes how the subjects of a relation are derived.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rewrite {
+    /// The subjects stored for the relation itself.
+    This,
+
+    /// The subjects of another relation of the same object, such as editors being viewers.
+    ComputedUserset(String),
+
+    /// The subjects of the `computed` relation of every object related through `tupleset`, such
+    /// as the editors of the parent folder of a document.
+    TupleToUserset { tupleset: String, computed: String },
+
+    /// The subjects of any of the rewrites.
+    Union(Vec<Rewrite>),
+}
+
+/// The TupleStore describes where relation tuples are stored.
+pub trait TupleStore {
+    /// Returns the subjects related to the object by the relation.
+    fn subjects(&self, object: &str, relation: &str) -> Vec<Subject>;
+}
+
+/// The InMemoryTupleStore describes relation tuples held in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryTupleStore {
+    tuples: BTreeSet<RelationTuple>,
+}
+
+/// The Relationships describes the relation tuples of a store along with the rewrites of each
+/// relation, keyed by the type of the object, such as `folder` of `folder:home`.
+#[derive(Debug)]
+pub struct Relationships<S> {
+    pub store: S,
+    rewrites: BTreeMap<(String, String), Rewrite>,
+}
+
+impl RelationTuple {
+    /// Parses `object#relation@subject`, or returns `None` if any part is missing.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (object, rest) = value.split_once('#')?;
+        let (relation, subject) = rest.split_once('@')?;
+
+        if object.is_empty() || relation.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            object: object.to_string(),
+            relation: relation.to_string(),
+            subject: Subject::parse(subject)?,
+        })
+    }
+}
+
+impl Subject {
+    /// Parses `object`, or `object#relation` for a userset, or returns `None` if any part is
+    /// missing.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.split_once('#') {
+            None if !value.is_empty() => Some(Subject::Object(value.to_string())),
+            Some((object, relation)) if !object.is_empty() && !relation.is_empty() => {
+                Some(Subject::Userset {
+                    object: object.to_string(),
+                    relation: relation.to_string(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn object(&self) -> &str {
+        match self {
+            Subject::Object(object) => object,
+            Subject::Userset { object, .. } => object,
+        }
+    }
+}
+
+impl fmt::Display for RelationTuple {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}@{}", self.object, self.relation, self.subject)
+    }
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Subject::Object(object) => f.write_str(object),
+            Subject::Userset { object, relation } => write!(f, "{}#{}", object, relation),
+        }
+    }
+}
+
+impl InMemoryTupleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write(&mut self, tuple: RelationTuple) {
+        self.tuples.insert(tuple);
+    }
+
+    pub fn delete(&mut self, tuple: &RelationTuple) {
+        self.tuples.remove(tuple);
+    }
+}
+
+impl TupleStore for InMemoryTupleStore {
+    fn subjects(&self, object: &str, relation: &str) -> Vec<Subject> {
+        self.tuples
+            .iter()
+            .filter(|tuple| tuple.object == object && tuple.relation == relation)
+            .map(|tuple| tuple.subject.clone())
+            .collect()
+    }
+}
+
+impl<S: TupleStore> Relationships<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            rewrites: BTreeMap::new(),
+        }
+    }
+
+    /// Sets how the `relation` of objects of the type is derived. Relations without a rewrite
+    /// only have the subjects stored for them.
+    pub fn rewrite(&mut self, object_type: &str, relation: &str, rewrite: Rewrite) {
+        self.rewrites
+            .insert((object_type.to_string(), relation.to_string()), rewrite);
+    }
+
+    /// Returns whether the subject, such as `user:alice`, has the relation to the object.
+    pub fn check(&self, object: &str, relation: &str, subject: &str) -> bool {
+        self.is_related(object, relation, subject, &mut BTreeSet::new())
+    }
+
+    fn is_related(
+        &self,
+        object: &str,
+        relation: &str,
+        subject: &str,
+        visited: &mut BTreeSet<(String, String)>,
+    ) -> bool {
+        // A relation already being resolved adds nothing new, which also breaks cycles.
+        if !visited.insert((object.to_string(), relation.to_string())) {
+            return false;
+        }
+
+        let object_type = object.split(':').next().unwrap_or_default();
+        let rewrite = self
+            .rewrites
+            .get(&(object_type.to_string(), relation.to_string()))
+            .unwrap_or(&Rewrite::This);
+
+        self.is_rewritten(rewrite, object, relation, subject, visited)
+    }
+
+    fn is_rewritten(
+        &self,
+        rewrite: &Rewrite,
+        object: &str,
+        relation: &str,
+        subject: &str,
+        visited: &mut BTreeSet<(String, String)>,
+    ) -> bool {
+        match rewrite {
+            Rewrite::This => {
+                self.store
+                    .subjects(object, relation)
+                    .iter()
+                    .any(|other| match other {
+                        Subject::Object(other) => other == subject,
+                        Subject::Userset { object, relation } => {
+                            self.is_related(object, relation, subject, visited)
+                        }
+                    })
+            }
+            Rewrite::ComputedUserset(computed) => {
+                self.is_related(object, computed, subject, visited)
+            }
+            Rewrite::TupleToUserset { tupleset, computed } => self
+                .store
+                .subjects(object, tupleset)
+                .iter()
+                .any(|related| self.is_related(related.object(), computed, subject, visited)),
+            Rewrite::Union(rewrites) => rewrites
+                .iter()
+                .any(|rewrite| self.is_rewritten(rewrite, object, relation, subject, visited)),
+        }
+    }
+}
+
+/// Evaluates the policies that apply to the principal against the action and resources.
+///
+/// Besides naming principals, a policy may name the subjects of a relationship: `object#relation`
+/// for those related to a given object, or `#relation` for those related to the resource being
+/// acted upon, such as `#writer` of `doc:readme`.
+pub fn is_authorized_with_relationships<'a, S: TupleStore>(
+    relationships: &Relationships<S>,
+    principal: &Principal,
+    policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> (Effect, Vec<&'a Policy>) {
+    let is_principal = |policy: &Policy| {
+        policy.principals.is_empty()
+            || policy.principals.iter().any(|other| {
+                if other.0 == "*" || other == principal {
+                    return true;
+                }
+
+                match other.0.split_once('#') {
+                    Some(("", relation)) => scoped_resources.iter().any(|scoped_resource| {
+                        relationships.check(&scoped_resource.to_string(), relation, &principal.0)
+                    }),
+                    Some((object, relation)) => relationships.check(object, relation, &principal.0),
+                    None => false,
+                }
+            })
+    };
+
+    let (decision, policies) = combine(&DenyOverrides, policies, |policy| {
+        is_principal(policy) && is_applicable(policy, scoped_action, scoped_resources)
+    });
+
+    (decision.effect(), policies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::principal_policy;
+
+    /// Documents are written by their writers, and by the editors of the folder containing them.
+    /// Folders are edited by their editors, and by the members of their editor groups.
+    fn relationships() -> Relationships<InMemoryTupleStore> {
+        let mut store = InMemoryTupleStore::new();
+        for tuple in &[
+            "doc:readme#parent@folder:home",
+            "doc:notes#writer@user:carol",
+            "folder:home#editor@user:alice",
+            "folder:home#editor@group:engineering#member",
+            "group:engineering#member@user:bob",
+            "group:engineering#member@group:admins#member",
+            "group:admins#member@group:engineering#member",
+        ] {
+            store.write(RelationTuple::parse(tuple).unwrap());
+        }
+
+        let mut relationships = Relationships::new(store);
+        relationships.rewrite(
+            "doc",
+            "writer",
+            Rewrite::Union(vec![
+                Rewrite::This,
+                Rewrite::TupleToUserset {
+                    tupleset: "parent".to_string(),
+                    computed: "editor".to_string(),
+                },
+            ]),
+        );
+        relationships.rewrite(
+            "doc",
+            "viewer",
+            Rewrite::Union(vec![
+                Rewrite::This,
+                Rewrite::ComputedUserset("writer".to_string()),
+            ]),
+        );
+        relationships
+    }
+
+    mod parse {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            for value in &["doc:1#owner@user:alice", "doc:1#viewer@group:a#member"] {
+                let actual = RelationTuple::parse(value).unwrap();
+
+                assert_eq!(&actual.to_string(), value);
+            }
+        }
+
+        #[test]
+        fn fail() {
+            for value in &["doc:1", "doc:1#owner", "#owner@user:alice", "doc:1#owner@"] {
+                assert_eq!(RelationTuple::parse(value), None);
+            }
+        }
+    }
+
+    mod check {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let relationships = relationships();
+
+            for (object, relation, subject) in &[
+                ("folder:home", "editor", "user:alice"),
+                ("doc:readme", "writer", "user:alice"),
+                ("doc:readme", "writer", "user:bob"),
+                ("doc:readme", "viewer", "user:bob"),
+                ("doc:notes", "viewer", "user:carol"),
+            ] {
+                assert!(
+                    relationships.check(object, relation, subject),
+                    "{}#{}@{}",
+                    object,
+                    relation,
+                    subject
+                );
+            }
+        }
+
+        #[test]
+        fn fail() {
+            let relationships = relationships();
+
+            for (object, relation, subject) in &[
+                ("doc:readme", "writer", "user:carol"),
+                ("doc:notes", "writer", "user:alice"),
+                ("doc:readme", "parent", "user:alice"),
+                ("group:admins", "member", "user:dave"),
+            ] {
+                assert!(
+                    !relationships.check(object, relation, subject),
+                    "{}#{}@{}",
+                    object,
+                    relation,
+                    subject
+                );
+            }
+        }
+
+        #[test]
+        fn pass_deleted() {
+            let mut relationships = relationships();
+            relationships
+                .store
+                .delete(&RelationTuple::parse("folder:home#editor@user:alice").unwrap());
+
+            assert!(!relationships.check("doc:readme", "writer", "user:alice"));
+        }
+    }
+
+    mod is_authorized_with_relationships {
+        use super::*;
+
+        fn evaluate(policies: &[Policy], user: &str, resource: &str) -> Effect {
+            let action = ScopedAction::parse("doc:write:document").unwrap();
+            let resources = vec![ScopedResource::parse(resource).unwrap()];
+
+            is_authorized_with_relationships(
+                &relationships(),
+                &Principal(user.to_string()),
+                policies,
+                &action,
+                &resources,
+            )
+            .0
+        }
+
+        #[test]
+        fn pass_resource_relation() {
+            let policies = vec![principal_policy(
+                Effect::Allow,
+                &["#writer"],
+                "doc:write:*",
+                "doc:*",
+            )];
+
+            assert_eq!(evaluate(&policies, "user:bob", "doc:readme"), Effect::Allow);
+            assert_eq!(evaluate(&policies, "user:bob", "doc:notes"), Effect::Deny);
+        }
+
+        #[test]
+        fn pass_object_relation() {
+            let policies = vec![principal_policy(
+                Effect::Allow,
+                &["group:engineering#member"],
+                "doc:write:*",
+                "doc:*",
+            )];
+
+            assert_eq!(evaluate(&policies, "user:bob", "doc:notes"), Effect::Allow);
+            assert_eq!(evaluate(&policies, "user:alice", "doc:notes"), Effect::Deny);
+        }
+    }
+}
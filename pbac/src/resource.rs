@@ -1,46 +1,85 @@
-use crate::ElementParseError;
+use crate::parser::{escape, Parser};
+use crate::{ElementParseError, Part};
+use std::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ScopedResource {
     pub scope: String,
-    pub resource: String,
+
+    /// The `:` separated segments of the resource, such as `region:account:type/id`.
+    pub segments: Vec<String>,
+
+    /// The type of the resource, which an action declares as its resource, such as `document` for
+    /// `docs:read:document`. A parsed resource is untyped, whatever its identifier looks like.
+    pub resource_type: Option<String>,
 }
 
 impl ScopedResource {
     pub fn parse(value: &str) -> Result<Self, ElementParseError> {
-        match value.find(':') {
-            None => Err(ElementParseError {
-                token: value.to_string(),
-            }),
-            Some(index) => {
-                let scope = &value[0..index];
-
-                if scope.is_empty() {
-                    return Err(ElementParseError {
-                        token: scope.to_string(),
-                    });
-                }
-
-                let resource = &value[index + 1..value.len()];
-
-                if resource.is_empty() {
-                    return Err(ElementParseError {
-                        token: resource.to_string(),
-                    });
-                }
-
-                Ok(Self {
-                    scope: scope.to_string(),
-                    resource: resource.to_string(),
-                })
-            }
+        let mut parser = Parser::new(value);
+
+        Ok(Self {
+            scope: parser.segment(Part::Scope)?.into(),
+            segments: parser
+                .segments(Part::Resource)?
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            resource_type: None,
+        })
+    }
+
+    /// Returns the resource of the scope, splitting the resource into segments at each `:`, as in
+    /// `region:account:type/id`.
+    ///
+    /// This builds the resource from the single string a `ScopedResource` held before it was split
+    /// into segments.
+    pub fn new(scope: &str, resource: &str) -> Self {
+        Self {
+            scope: scope.to_string(),
+            segments: resource.split(':').map(String::from).collect(),
+            resource_type: None,
+        }
+    }
+
+    /// Returns the segments joined by `:`, the single string a `ScopedResource` held before it was
+    /// split into segments.
+    pub fn resource(&self) -> String {
+        self.segments.join(":")
+    }
+
+    /// Returns the resource with the type, which is checked against the resource the action
+    /// declares.
+    pub fn with_type(self, resource_type: &str) -> Self {
+        Self {
+            resource_type: Some(resource_type.to_string()),
+            ..self
+        }
+    }
+
+    /// Returns the type of the resource, or `None` for an untyped resource.
+    pub fn resource_type(&self) -> Option<&str> {
+        self.resource_type.as_deref()
+    }
+}
+
+impl fmt::Display for ScopedResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        escape(f, &self.scope)?;
+
+        for segment in &self.segments {
+            f.write_str(":")?;
+            escape(f, segment)?;
         }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ParseErrorKind;
 
     mod parse {
         use super::*;
@@ -49,7 +88,8 @@ mod tests {
         fn pass() {
             let expected = ScopedResource {
                 scope: "scope".to_string(),
-                resource: "resource".to_string(),
+                segments: vec!["resource".to_string()],
+                resource_type: None,
             };
 
             let actual = ScopedResource::parse("scope:resource").unwrap();
@@ -57,10 +97,45 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn pass_segments() {
+            let expected = ScopedResource {
+                scope: "scope".to_string(),
+                segments: vec![
+                    "region".to_string(),
+                    "account".to_string(),
+                    "type/id".to_string(),
+                ],
+                resource_type: None,
+            };
+
+            let actual = ScopedResource::parse("scope:region:account:type/id").unwrap();
+
+            assert_eq!(actual, expected);
+            assert_eq!(actual.to_string(), "scope:region:account:type/id");
+        }
+
+        #[test]
+        fn fail_segment_empty() {
+            let expected = ElementParseError {
+                kind: ParseErrorKind::EmptySegment,
+                offset: 13,
+                part: Part::Resource,
+                input: "scope:region::type/id".to_string(),
+            };
+
+            let actual = ScopedResource::parse("scope:region::type/id").unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
         #[test]
         fn fail_scope_empty() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 6,
+                part: Part::Resource,
+                input: "scope:".to_string(),
             };
 
             let actual = ScopedResource::parse("scope:").unwrap_err();
@@ -71,7 +146,10 @@ mod tests {
         #[test]
         fn fail_empty_resource() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: ":resource".to_string(),
             };
 
             let actual = ScopedResource::parse(":resource").unwrap_err();
@@ -82,7 +160,10 @@ mod tests {
         #[test]
         fn fail_empty_empty() {
             let expected = ElementParseError {
-                token: "".to_string(),
+                kind: ParseErrorKind::EmptySegment,
+                offset: 0,
+                part: Part::Scope,
+                input: ":".to_string(),
             };
 
             let actual = ScopedResource::parse(":").unwrap_err();
@@ -93,7 +174,10 @@ mod tests {
         #[test]
         fn fail_token() {
             let expected = ElementParseError {
-                token: "token".to_string(),
+                kind: ParseErrorKind::MissingSeparator,
+                offset: 5,
+                part: Part::Scope,
+                input: "token".to_string(),
             };
 
             let actual = ScopedResource::parse("token").unwrap_err();
@@ -101,4 +185,98 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    mod new {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let expected = ScopedResource::parse("scope:region:account:type/id").unwrap();
+
+            let actual = ScopedResource::new("scope", "region:account:type/id");
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod resource {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let resource = ScopedResource::parse("scope:region:account:type/id").unwrap();
+
+            assert_eq!(resource.resource(), "region:account:type/id");
+        }
+
+        #[test]
+        fn pass_single() {
+            let resource = ScopedResource::parse("scope:resource").unwrap();
+
+            assert_eq!(resource.resource(), "resource");
+        }
+    }
+
+    mod escaped {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let expected = ScopedResource {
+                scope: "urn".to_string(),
+                segments: vec!["isbn:0451450523".to_string()],
+                resource_type: None,
+            };
+
+            let actual = ScopedResource::parse(r"urn:isbn\:0451450523").unwrap();
+
+            assert_eq!(actual, expected);
+            assert_eq!(actual.to_string(), r"urn:isbn\:0451450523");
+        }
+    }
+
+    mod display {
+        use super::*;
+
+        #[test]
+        fn pass_control() {
+            let resource = ScopedResource {
+                scope: "a".to_string(),
+                segments: vec!["tab\there".to_string()],
+                resource_type: None,
+            };
+
+            let actual = resource.to_string();
+
+            assert_eq!(actual, r"a:tab\u{9}here");
+            assert_eq!(ScopedResource::parse(&actual).unwrap(), resource);
+        }
+    }
+
+    mod resource_type {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let resource = ScopedResource::parse("scope:id").unwrap().with_type("type");
+
+            assert_eq!(resource.resource_type(), Some("type"));
+            assert_eq!(resource.to_string(), "scope:id");
+        }
+
+        #[test]
+        fn pass_untyped() {
+            let resource = ScopedResource::parse("scope:resource").unwrap();
+
+            assert_eq!(resource.resource_type(), None);
+        }
+
+        #[test]
+        fn pass_slash() {
+            let resource = ScopedResource::parse("files:bucket/reports/2024.csv").unwrap();
+
+            assert_eq!(resource.resource_type(), None);
+            assert_eq!(resource.to_string(), "files:bucket/reports/2024.csv");
+        }
+    }
 }
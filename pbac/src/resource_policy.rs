@@ -0,0 +1,214 @@
+use crate::authorizer::{combine, is_applicable, is_principal};
+use crate::{Decision, DenyOverrides, Effect, Policy, Principal, ScopedAction, ScopedResource};
+
+/// The AttachedResource describes a resource along with the resource-based policies stored on it,
+/// such as the policy of a bucket.
+#[derive(Debug, PartialEq)]
+pub struct AttachedResource {
+    pub resource: ScopedResource,
+
+    /// The policies stating which principals may act upon the resource. A policy without
+    /// principals applies to the principals of the resource's scope only, so that principals of
+    /// other scopes must be named, or matched by `*`.
+    pub policies: Vec<Policy>,
+}
+
+/// The Caller describes the principal making a request, along with the scope it belongs to.
+#[derive(Debug, PartialEq)]
+pub struct Caller {
+    pub principal: Principal,
+    pub scope: String,
+}
+
+/// Evaluates the identity policies of the caller together with the resource-based policies
+/// attached to the resource.
+///
+/// An explicit deny in either wins. Within the caller's own scope, an allow from either the
+/// identity or the resource-based policies is enough. Across scopes, both must allow.
+pub fn is_authorized_with_resource_policies<'a>(
+    caller: &Caller,
+    identity_policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    attached_resource: &'a AttachedResource,
+) -> (Effect, Vec<&'a Policy>) {
+    let scoped_resources = std::slice::from_ref(&attached_resource.resource);
+
+    let (identity_decision, identity_matches) =
+        combine(&DenyOverrides, identity_policies, |policy| {
+            is_applicable(policy, scoped_action, scoped_resources)
+        });
+
+    let same_scope = caller.scope == attached_resource.resource.scope;
+
+    let (resource_decision, resource_matches) =
+        combine(&DenyOverrides, &attached_resource.policies, |policy| {
+            let principal_match = if policy.principals.is_empty() {
+                same_scope
+            } else {
+                is_principal(policy, std::slice::from_ref(&caller.principal))
+            };
+
+            principal_match && is_applicable(policy, scoped_action, scoped_resources)
+        });
+
+    match (identity_decision, resource_decision) {
+        (Decision::Deny, Decision::Deny) => {
+            (Effect::Deny, [identity_matches, resource_matches].concat())
+        }
+        (Decision::Deny, _) => (Effect::Deny, identity_matches),
+        (_, Decision::Deny) => (Effect::Deny, resource_matches),
+        (Decision::Allow, Decision::Allow) => {
+            (Effect::Allow, [identity_matches, resource_matches].concat())
+        }
+        (Decision::Allow, _) if same_scope => (Effect::Allow, identity_matches),
+        (_, Decision::Allow) if same_scope => (Effect::Allow, resource_matches),
+        _ => (Effect::Deny, vec![]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::principal_policy;
+
+    fn bucket(policies: Vec<Policy>) -> AttachedResource {
+        AttachedResource {
+            resource: ScopedResource::parse("storage:bucket/logs").unwrap(),
+            policies,
+        }
+    }
+
+    fn caller(scope: &str) -> Caller {
+        Caller {
+            principal: Principal("user/alice".to_string()),
+            scope: scope.to_string(),
+        }
+    }
+
+    fn evaluate<'a>(
+        caller: &Caller,
+        identity_policies: &'a [Policy],
+        attached_resource: &'a AttachedResource,
+    ) -> (Effect, Vec<&'a Policy>) {
+        let action = ScopedAction::parse("storage:read:bucket").unwrap();
+
+        is_authorized_with_resource_policies(caller, identity_policies, &action, attached_resource)
+    }
+
+    mod is_authorized_with_resource_policies {
+        use super::*;
+
+        #[test]
+        fn pass_same_scope_identity() {
+            let identity = vec![principal_policy(Effect::Allow, &[], "storage:*", "*")];
+            let resource = bucket(vec![]);
+
+            let actual = evaluate(&caller("storage"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Allow, vec![&identity[0]]));
+        }
+
+        #[test]
+        fn pass_same_scope_resource() {
+            let identity = vec![];
+            let resource = bucket(vec![principal_policy(
+                Effect::Allow,
+                &["user/alice"],
+                "*",
+                "*",
+            )]);
+
+            let actual = evaluate(&caller("storage"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Allow, vec![&resource.policies[0]]));
+        }
+
+        #[test]
+        fn pass_cross_scope() {
+            let identity = vec![principal_policy(Effect::Allow, &[], "storage:*", "*")];
+            let resource = bucket(vec![principal_policy(Effect::Allow, &["*"], "*", "*")]);
+
+            let actual = evaluate(&caller("billing"), &identity, &resource);
+
+            assert_eq!(
+                actual,
+                (Effect::Allow, vec![&identity[0], &resource.policies[0]])
+            );
+        }
+
+        #[test]
+        fn pass_same_scope_unnamed() {
+            let identity = vec![];
+            let resource = bucket(vec![principal_policy(Effect::Allow, &[], "*", "*")]);
+
+            let actual = evaluate(&caller("storage"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Allow, vec![&resource.policies[0]]));
+        }
+
+        #[test]
+        fn fail_cross_scope_unnamed() {
+            let identity = vec![principal_policy(Effect::Allow, &[], "storage:*", "*")];
+            let resource = bucket(vec![principal_policy(Effect::Allow, &[], "*", "*")]);
+
+            let actual = evaluate(&caller("billing"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Deny, vec![]));
+        }
+
+        #[test]
+        fn fail_cross_scope_identity_only() {
+            let identity = vec![principal_policy(Effect::Allow, &[], "storage:*", "*")];
+            let resource = bucket(vec![]);
+
+            let actual = evaluate(&caller("billing"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Deny, vec![]));
+        }
+
+        #[test]
+        fn fail_cross_scope_resource_only() {
+            let identity = vec![];
+            let resource = bucket(vec![principal_policy(
+                Effect::Allow,
+                &["user/alice"],
+                "*",
+                "*",
+            )]);
+
+            let actual = evaluate(&caller("billing"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Deny, vec![]));
+        }
+
+        #[test]
+        fn fail_other_principal() {
+            let identity = vec![];
+            let resource = bucket(vec![principal_policy(
+                Effect::Allow,
+                &["user/bob"],
+                "*",
+                "*",
+            )]);
+
+            let actual = evaluate(&caller("storage"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Deny, vec![]));
+        }
+
+        #[test]
+        fn fail_resource_deny() {
+            let identity = vec![principal_policy(Effect::Allow, &[], "*", "*")];
+            let resource = bucket(vec![principal_policy(
+                Effect::Deny,
+                &["user/alice"],
+                "*",
+                "*",
+            )]);
+
+            let actual = evaluate(&caller("storage"), &identity, &resource);
+
+            assert_eq!(actual, (Effect::Deny, vec![&resource.policies[0]]));
+        }
+    }
+}
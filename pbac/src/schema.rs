@@ -0,0 +1,191 @@
+use crate::{Policy, ResourceDocument, ScopedResource};
+use std::collections::BTreeMap;
+
+/// The ResourceSchema describes the segments that the resources of each scope have.
+#[derive(Debug, Default)]
+pub struct ResourceSchema {
+    scopes: BTreeMap<String, Vec<String>>,
+}
+
+/// The SegmentCountMismatch describes a resource whose number of segments differs from the number
+/// its scope declares.
+#[derive(Debug, PartialEq)]
+pub struct SegmentCountMismatch {
+    /// The resource, or resource document, that was validated.
+    pub resource: String,
+
+    /// The names of the segments the scope declares.
+    pub expected: Vec<String>,
+
+    /// The number of segments the resource has.
+    pub actual: usize,
+}
+
+impl ResourceSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the names of the segments, such as `region`, `account` and `resource`, that the
+    /// resources of the `scope` have.
+    pub fn register(&mut self, scope: &str, segments: &[&str]) {
+        self.scopes.insert(
+            scope.to_string(),
+            segments.iter().map(|segment| segment.to_string()).collect(),
+        );
+    }
+
+    /// Checks that the resource has as many segments as its scope declares. Resources of scopes
+    /// that have not been registered are not checked.
+    pub fn validate_resource(&self, resource: &ScopedResource) -> Result<(), SegmentCountMismatch> {
+        self.validate(
+            resource.to_string(),
+            Some(&resource.scope),
+            Some(resource.segments.len()),
+        )
+    }
+
+    /// Checks that the document has as many segments as its scope declares. Documents with a
+    /// wildcard scope, or a lone wildcard for the segments, are not checked.
+    pub fn validate_document(
+        &self,
+        document: &ResourceDocument,
+    ) -> Result<(), SegmentCountMismatch> {
+        let (scope, segments) = document.pattern();
+
+        self.validate(document.to_string(), scope, segments.map(|s| s.len()))
+    }
+
+    /// Returns the resource documents of the policy whose number of segments is wrong.
+    pub fn validate_policy(&self, policy: &Policy) -> Vec<SegmentCountMismatch> {
+        policy
+            .resources
+            .iter()
+            .filter_map(|document| self.validate_document(document).err())
+            .collect()
+    }
+
+    fn validate(
+        &self,
+        resource: String,
+        scope: Option<&str>,
+        count: Option<usize>,
+    ) -> Result<(), SegmentCountMismatch> {
+        let expected = match scope.and_then(|scope| self.scopes.get(scope)) {
+            None => return Ok(()),
+            Some(expected) => expected,
+        };
+
+        match count {
+            Some(actual) if actual != expected.len() => Err(SegmentCountMismatch {
+                resource,
+                expected: expected.clone(),
+                actual,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActionDocument, Effect, Element};
+
+    fn schema() -> ResourceSchema {
+        let mut schema = ResourceSchema::new();
+        schema.register("storage", &["region", "account", "resource"]);
+        schema
+    }
+
+    mod validate_resource {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let resource = ScopedResource::parse("storage:eu:123:bucket/logs").unwrap();
+
+            let actual = schema().validate_resource(&resource);
+
+            assert_eq!(actual, Ok(()));
+        }
+
+        #[test]
+        fn pass_unregistered() {
+            let resource = ScopedResource::parse("docs:readme").unwrap();
+
+            let actual = schema().validate_resource(&resource);
+
+            assert_eq!(actual, Ok(()));
+        }
+
+        #[test]
+        fn fail() {
+            let expected = SegmentCountMismatch {
+                resource: "storage:bucket/logs".to_string(),
+                expected: vec![
+                    "region".to_string(),
+                    "account".to_string(),
+                    "resource".to_string(),
+                ],
+                actual: 1,
+            };
+
+            let resource = ScopedResource::parse("storage:bucket/logs").unwrap();
+
+            let actual = schema().validate_resource(&resource).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod validate_document {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            for value in &["*", "storage:*", "*:eu", "storage:*:123:*"] {
+                let document = ResourceDocument::parse(value).unwrap();
+
+                let actual = schema().validate_document(&document);
+
+                assert_eq!(actual, Ok(()));
+            }
+        }
+
+        #[test]
+        fn fail() {
+            let document = ResourceDocument::parse("storage:eu:*").unwrap();
+
+            let actual = schema().validate_document(&document).unwrap_err();
+
+            assert_eq!(actual.resource, "storage:eu:*");
+            assert_eq!(actual.actual, 2);
+        }
+    }
+
+    mod validate_policy {
+        use super::*;
+
+        #[test]
+        fn fail_documents() {
+            let policy = Policy {
+                actions: vec![ActionDocument::parse("*").unwrap()],
+                effect: Effect::Allow,
+                principals: vec![],
+                resources: vec![
+                    ResourceDocument::parse("storage:eu:123:*").unwrap(),
+                    ResourceDocument::parse("storage:eu:*").unwrap(),
+                    ResourceDocument::parse("storage:*:*:*:*").unwrap(),
+                ],
+            };
+
+            let actual = schema().validate_policy(&policy);
+
+            assert_eq!(
+                actual.iter().map(|m| m.actual).collect::<Vec<_>>(),
+                vec![2, 4]
+            );
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use crate::boundary::intersect;
+use crate::{Effect, Policy, ScopedAction, ScopedResource};
+
+/// The SessionDecision describes the effect of a principal's policies narrowed by the policies of
+/// a session.
+#[derive(Debug, PartialEq)]
+pub struct SessionDecision<'a> {
+    pub effect: Effect,
+
+    /// The principal and session policies that determined the effect.
+    pub policies: Vec<&'a Policy>,
+
+    /// Whether the principal's policies allowed the request, but the session policies did not.
+    pub narrowed: bool,
+}
+
+/// Evaluates the policies of a principal for a request made within a session, such as a service
+/// acting on behalf of a user with reduced rights.
+///
+/// The session policies are passed inline with the request and can only narrow access, the
+/// request is allowed only if both the principal's policies and the session policies allow it.
+/// An explicit deny in either wins.
+pub fn is_authorized_in_session<'a>(
+    principal_policies: &'a [Policy],
+    session_policies: &'a [Policy],
+    scoped_action: &ScopedAction,
+    scoped_resources: &[ScopedResource],
+) -> SessionDecision<'a> {
+    let (effect, policies, narrowed) = intersect(
+        principal_policies,
+        session_policies,
+        scoped_action,
+        scoped_resources,
+    );
+
+    SessionDecision {
+        effect,
+        policies,
+        narrowed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+
+    fn evaluate<'a>(
+        principal_policies: &'a [Policy],
+        session_policies: &'a [Policy],
+        action: &str,
+        resource: &str,
+    ) -> SessionDecision<'a> {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse(resource).unwrap()];
+
+        is_authorized_in_session(principal_policies, session_policies, &action, &resources)
+    }
+
+    mod is_authorized_in_session {
+        use super::*;
+
+        #[test]
+        fn pass_intersection() {
+            let principal = vec![policy(Effect::Allow, "docs:*", "*")];
+            let session = vec![policy(Effect::Allow, "*", "docs:document/readme")];
+
+            let actual = evaluate(
+                &principal,
+                &session,
+                "docs:read:document",
+                "docs:document/readme",
+            );
+
+            assert_eq!(
+                actual,
+                SessionDecision {
+                    effect: Effect::Allow,
+                    policies: vec![&principal[0], &session[0]],
+                    narrowed: false,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_narrowed() {
+            let principal = vec![policy(Effect::Allow, "docs:*", "*")];
+            let session = vec![policy(Effect::Allow, "*", "docs:document/readme")];
+
+            let actual = evaluate(
+                &principal,
+                &session,
+                "docs:read:document",
+                "docs:document/secret",
+            );
+
+            assert_eq!(
+                actual,
+                SessionDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    narrowed: true,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_session_does_not_expand() {
+            let principal = vec![policy(Effect::Allow, "docs:read:*", "*")];
+            let session = vec![policy(Effect::Allow, "*", "*")];
+
+            let actual = evaluate(
+                &principal,
+                &session,
+                "docs:write:document",
+                "docs:document/readme",
+            );
+
+            assert_eq!(
+                actual,
+                SessionDecision {
+                    effect: Effect::Deny,
+                    policies: vec![],
+                    narrowed: false,
+                }
+            );
+        }
+
+        #[test]
+        fn fail_session_deny() {
+            let principal = vec![policy(Effect::Allow, "*", "*")];
+            let session = vec![
+                policy(Effect::Allow, "*", "*"),
+                policy(Effect::Deny, "docs:write:*", "*"),
+            ];
+
+            let actual = evaluate(
+                &principal,
+                &session,
+                "docs:write:document",
+                "docs:document/readme",
+            );
+
+            assert_eq!(
+                actual,
+                SessionDecision {
+                    effect: Effect::Deny,
+                    policies: vec![&session[1]],
+                    narrowed: true,
+                }
+            );
+        }
+    }
+}
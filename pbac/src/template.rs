@@ -0,0 +1,638 @@
+use crate::parser::ESCAPED;
+use crate::{
+    ActionDocument, Effect, Element, ElementParseError, Policy, Principal, ResourceDocument,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// The PolicyTemplate describes policies with `{name}` placeholders, such as `docs:{tenant}:*`,
+/// that are instantiated once per set of arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolicyTemplate {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub policies: Vec<TemplatePolicy>,
+}
+
+/// The Parameter describes a value that a template is instantiated with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub kind: ParameterType,
+
+    /// A pattern, in which `*` matches any characters, that the argument must match.
+    pub pattern: Option<String>,
+}
+
+/// The ParameterType describes the values a parameter accepts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParameterType {
+    String,
+
+    /// One or more digits.
+    Integer,
+}
+
+/// The TemplatePolicy describes a policy whose elements may contain placeholders.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplatePolicy {
+    pub actions: Vec<String>,
+    pub effect: Effect,
+    pub principals: Vec<String>,
+    pub resources: Vec<String>,
+}
+
+/// The TemplateInstance describes the policies instantiated from a template, along with the
+/// template and arguments they came from so that they can be instantiated again when the template
+/// is updated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateInstance {
+    /// The name of the template of origin.
+    pub template: String,
+
+    pub arguments: BTreeMap<String, String>,
+    pub policies: Vec<Policy>,
+}
+
+/// The TemplateError describes why a template could not be instantiated.
+#[derive(Debug, PartialEq)]
+pub struct TemplateError {
+    /// The name of the template.
+    pub template: String,
+
+    pub kind: TemplateErrorKind,
+}
+
+/// The TemplateErrorKind describes the kind of template error.
+#[derive(Debug, PartialEq)]
+pub enum TemplateErrorKind {
+    /// A declared parameter was given no argument.
+    MissingArgument { parameter: String },
+
+    /// An argument was given for a parameter the template does not declare.
+    UnknownArgument { parameter: String },
+
+    /// The argument is empty, or contains whitespace or a control character.
+    InvalidValue { parameter: String },
+
+    /// The argument is not of the type of the parameter.
+    InvalidType {
+        parameter: String,
+        expected: ParameterType,
+    },
+
+    /// The argument does not match the pattern of the parameter.
+    PatternMismatch { parameter: String, pattern: String },
+
+    /// The argument would make a principal stand for every principal, or for a relationship.
+    InvalidPrincipal { parameter: String },
+
+    /// A placeholder names no declared parameter, or is missing its closing `}`.
+    UnknownPlaceholder { placeholder: String },
+
+    /// The template being re-instantiated is not the template of origin.
+    TemplateMismatch { origin: String },
+
+    /// An element is not valid once the arguments are substituted.
+    Parse(ElementParseError),
+}
+
+impl PolicyTemplate {
+    /// Instantiates the policies of the template with the arguments, given as parameter name and
+    /// value pairs.
+    ///
+    /// Arguments are substituted as literals, so that an argument of `*` cannot widen what the
+    /// policies cover. An argument may not turn a principal into `*`, nor add a `#` to it.
+    pub fn instantiate(
+        &self,
+        arguments: &[(&str, &str)],
+    ) -> Result<TemplateInstance, TemplateError> {
+        let arguments = arguments
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+
+        self.instantiate_with(arguments)
+    }
+
+    fn instantiate_with(
+        &self,
+        arguments: BTreeMap<String, String>,
+    ) -> Result<TemplateInstance, TemplateError> {
+        let error = |kind| TemplateError {
+            template: self.name.clone(),
+            kind,
+        };
+
+        self.validate(&arguments).map_err(error)?;
+
+        let policies = self
+            .policies
+            .iter()
+            .map(|policy| policy.instantiate(&arguments))
+            .collect::<Result<_, _>>()
+            .map_err(error)?;
+
+        Ok(TemplateInstance {
+            template: self.name.clone(),
+            arguments,
+            policies,
+        })
+    }
+
+    fn validate(&self, arguments: &BTreeMap<String, String>) -> Result<(), TemplateErrorKind> {
+        if let Some(name) = arguments
+            .keys()
+            .find(|name| !self.parameters.iter().any(|p| &&p.name == name))
+        {
+            return Err(TemplateErrorKind::UnknownArgument {
+                parameter: name.clone(),
+            });
+        }
+
+        for parameter in &self.parameters {
+            let value = arguments.get(&parameter.name).ok_or_else(|| {
+                TemplateErrorKind::MissingArgument {
+                    parameter: parameter.name.clone(),
+                }
+            })?;
+
+            parameter.validate(value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Parameter {
+    fn validate(&self, value: &str) -> Result<(), TemplateErrorKind> {
+        if value.is_empty() || value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(TemplateErrorKind::InvalidValue {
+                parameter: self.name.clone(),
+            });
+        }
+
+        let is_type = match self.kind {
+            ParameterType::String => true,
+            ParameterType::Integer => value.chars().all(|c| c.is_ascii_digit()),
+        };
+
+        if !is_type {
+            return Err(TemplateErrorKind::InvalidType {
+                parameter: self.name.clone(),
+                expected: self.kind,
+            });
+        }
+
+        match &self.pattern {
+            Some(pattern) if !is_glob_match(pattern, value) => {
+                Err(TemplateErrorKind::PatternMismatch {
+                    parameter: self.name.clone(),
+                    pattern: pattern.clone(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TemplatePolicy {
+    fn instantiate(
+        &self,
+        arguments: &BTreeMap<String, String>,
+    ) -> Result<Policy, TemplateErrorKind> {
+        let parse = |value: String| ActionDocument::parse(&value).map_err(TemplateErrorKind::Parse);
+        let actions = self
+            .actions
+            .iter()
+            .map(|action| substitute(action, arguments, Target::Element).and_then(parse))
+            .collect::<Result<_, _>>()?;
+
+        let parse =
+            |value: String| ResourceDocument::parse(&value).map_err(TemplateErrorKind::Parse);
+        let resources = self
+            .resources
+            .iter()
+            .map(|resource| substitute(resource, arguments, Target::Element).and_then(parse))
+            .collect::<Result<_, _>>()?;
+
+        let principals = self
+            .principals
+            .iter()
+            .map(|principal| substitute(principal, arguments, Target::Principal).map(Principal))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Policy {
+            actions,
+            effect: self.effect,
+            principals,
+            resources,
+        })
+    }
+}
+
+impl TemplateInstance {
+    /// Instantiates the template again with the same arguments, such as after the template has
+    /// been updated. The template must have the same name as the template of origin.
+    pub fn reroll(&self, template: &PolicyTemplate) -> Result<TemplateInstance, TemplateError> {
+        if template.name != self.template {
+            return Err(TemplateError {
+                template: template.name.clone(),
+                kind: TemplateErrorKind::TemplateMismatch {
+                    origin: self.template.clone(),
+                },
+            });
+        }
+
+        template.instantiate_with(self.arguments.clone())
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "template {}: ", self.template)?;
+
+        match &self.kind {
+            TemplateErrorKind::MissingArgument { parameter } => {
+                write!(f, "missing argument for {}", parameter)
+            }
+            TemplateErrorKind::UnknownArgument { parameter } => {
+                write!(f, "unknown parameter {}", parameter)
+            }
+            TemplateErrorKind::InvalidValue { parameter } => {
+                write!(f, "invalid value for {}", parameter)
+            }
+            TemplateErrorKind::InvalidType {
+                parameter,
+                expected,
+            } => write!(f, "{} must be of type {:?}", parameter, expected),
+            TemplateErrorKind::PatternMismatch { parameter, pattern } => {
+                write!(f, "{} must match {}", parameter, pattern)
+            }
+            TemplateErrorKind::InvalidPrincipal { parameter } => {
+                write!(f, "invalid principal for {}", parameter)
+            }
+            TemplateErrorKind::UnknownPlaceholder { placeholder } => {
+                write!(f, "unknown placeholder {}", placeholder)
+            }
+            TemplateErrorKind::TemplateMismatch { origin } => {
+                write!(f, "policies were instantiated from {}", origin)
+            }
+            TemplateErrorKind::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+/// The Target describes what the text a placeholder is substituted into is parsed as.
+#[derive(Clone, Copy, PartialEq)]
+enum Target {
+    /// An action or resource document, in which the characters the parser gives meaning to are
+    /// escaped.
+    Element,
+
+    /// A principal, which is taken as written, so the arguments may not make it `*` or add a `#`.
+    Principal,
+}
+
+/// Replaces the `{name}` placeholders of the text with the arguments, as literals of the target.
+fn substitute(
+    text: &str,
+    arguments: &BTreeMap<String, String>,
+    target: Target,
+) -> Result<String, TemplateErrorKind> {
+    let mut substituted = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        substituted.push_str(&rest[..start]);
+
+        let unknown = |placeholder: &str| TemplateErrorKind::UnknownPlaceholder {
+            placeholder: placeholder.to_string(),
+        };
+
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| unknown(&rest[start..]))?;
+
+        let parameter = &rest[start + 1..end];
+        let value = arguments
+            .get(parameter)
+            .ok_or_else(|| unknown(&rest[start..=end]))?;
+
+        let is_principal = target == Target::Principal;
+
+        // Values are not empty, so a principal is only `*` if the argument is all of it.
+        if is_principal && (value.contains('#') || (value == "*" && text == &rest[start..=end])) {
+            return Err(TemplateErrorKind::InvalidPrincipal {
+                parameter: parameter.to_string(),
+            });
+        }
+
+        for character in value.chars() {
+            if !is_principal && ESCAPED.contains(&character) {
+                substituted.push('\\');
+            }
+
+            substituted.push(character);
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    substituted.push_str(rest);
+
+    Ok(substituted)
+}
+
+/// Returns whether the value matches the pattern, in which `*` matches any characters.
+fn is_glob_match(pattern: &str, value: &str) -> bool {
+    let parts = pattern.split('*').collect::<Vec<_>>();
+
+    let (first, last) = match parts.as_slice() {
+        [only] => return *only == value,
+        [first, .., last] => (*first, *last),
+        [] => unreachable!(),
+    };
+
+    if value.len() < first.len() + last.len() || !value.starts_with(first) || !value.ends_with(last)
+    {
+        return false;
+    }
+
+    let mut rest = &value[first.len()..value.len() - last.len()];
+
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            None => return false,
+            Some(index) => rest = &rest[index + part.len()..],
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScopedResource;
+
+    fn template(resource: &str) -> PolicyTemplate {
+        PolicyTemplate {
+            name: "TenantReadOnly".to_string(),
+            parameters: vec![
+                Parameter {
+                    name: "tenant".to_string(),
+                    kind: ParameterType::String,
+                    pattern: Some("t-*".to_string()),
+                },
+                Parameter {
+                    name: "account".to_string(),
+                    kind: ParameterType::Integer,
+                    pattern: None,
+                },
+            ],
+            policies: vec![TemplatePolicy {
+                actions: vec!["docs:read:*".to_string()],
+                effect: Effect::Allow,
+                principals: vec!["group:{tenant}/readers".to_string()],
+                resources: vec![resource.to_string()],
+            }],
+        }
+    }
+
+    fn error(kind: TemplateErrorKind) -> TemplateError {
+        TemplateError {
+            template: "TenantReadOnly".to_string(),
+            kind,
+        }
+    }
+
+    fn instantiate(
+        resource: &str,
+        arguments: &[(&str, &str)],
+    ) -> Result<TemplateInstance, TemplateError> {
+        template(resource).instantiate(arguments)
+    }
+
+    mod instantiate {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let expected = Policy {
+                actions: vec![ActionDocument::parse("docs:read:*").unwrap()],
+                effect: Effect::Allow,
+                principals: vec![Principal("group:t-acme/readers".to_string())],
+                resources: vec![ResourceDocument::parse("docs:42:t-acme:*").unwrap()],
+            };
+
+            let actual = instantiate(
+                "docs:{account}:{tenant}:*",
+                &[("tenant", "t-acme"), ("account", "42")],
+            )
+            .unwrap();
+
+            assert_eq!(actual.template, "TenantReadOnly");
+            assert_eq!(actual.policies, vec![expected]);
+        }
+
+        #[test]
+        fn pass_escaped() {
+            let mut template = template("docs:{tenant}");
+            template.parameters[0].pattern = None;
+
+            let actual = template
+                .instantiate(&[("tenant", "*"), ("account", "42")])
+                .unwrap();
+
+            let other = ScopedResource::parse("docs:other").unwrap();
+
+            assert!(!actual.policies[0].resources[0].is_match(&other));
+        }
+
+        #[test]
+        fn fail_missing() {
+            let expected = error(TemplateErrorKind::MissingArgument {
+                parameter: "account".to_string(),
+            });
+
+            let actual = instantiate("docs:*", &[("tenant", "t-acme")]).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_unknown() {
+            let expected = error(TemplateErrorKind::UnknownArgument {
+                parameter: "region".to_string(),
+            });
+
+            let actual = instantiate(
+                "docs:*",
+                &[("tenant", "t-acme"), ("account", "42"), ("region", "eu")],
+            )
+            .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_type() {
+            let expected = error(TemplateErrorKind::InvalidType {
+                parameter: "account".to_string(),
+                expected: ParameterType::Integer,
+            });
+
+            let actual =
+                instantiate("docs:*", &[("tenant", "t-acme"), ("account", "4x")]).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_pattern() {
+            let expected = error(TemplateErrorKind::PatternMismatch {
+                parameter: "tenant".to_string(),
+                pattern: "t-*".to_string(),
+            });
+
+            let actual =
+                instantiate("docs:*", &[("tenant", "acme"), ("account", "42")]).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_value() {
+            let expected = error(TemplateErrorKind::InvalidValue {
+                parameter: "tenant".to_string(),
+            });
+
+            let actual =
+                instantiate("docs:*", &[("tenant", "t- acme"), ("account", "42")]).unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_principal_wildcard() {
+            let expected = error(TemplateErrorKind::InvalidPrincipal {
+                parameter: "user".to_string(),
+            });
+
+            let mut template = template("docs:*");
+            template.parameters.push(Parameter {
+                name: "user".to_string(),
+                kind: ParameterType::String,
+                pattern: None,
+            });
+            template.policies[0].principals = vec!["{user}".to_string()];
+
+            let actual = template
+                .instantiate(&[("tenant", "t-acme"), ("account", "42"), ("user", "*")])
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_principal_relationship() {
+            let expected = error(TemplateErrorKind::InvalidPrincipal {
+                parameter: "tenant".to_string(),
+            });
+
+            let actual = instantiate("docs:*", &[("tenant", "t-acme#owner"), ("account", "42")])
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_placeholder() {
+            let expected = error(TemplateErrorKind::UnknownPlaceholder {
+                placeholder: "{region}".to_string(),
+            });
+
+            let actual = instantiate("docs:{region}", &[("tenant", "t-acme"), ("account", "42")])
+                .unwrap_err();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn fail_parse() {
+            let actual = instantiate("{tenant}", &[("tenant", "t-acme"), ("account", "42")]);
+
+            assert!(matches!(
+                actual,
+                Err(TemplateError {
+                    kind: TemplateErrorKind::Parse(_),
+                    ..
+                })
+            ));
+        }
+    }
+
+    mod reroll {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            let instance = instantiate(
+                "docs:{account}:{tenant}:*",
+                &[("tenant", "t-acme"), ("account", "42")],
+            )
+            .unwrap();
+
+            let actual = instance
+                .reroll(&template("docs:{account}:{tenant}:reports:*"))
+                .unwrap();
+
+            assert_eq!(actual.arguments, instance.arguments);
+            assert_eq!(
+                actual.policies[0].resources,
+                vec![ResourceDocument::parse("docs:42:t-acme:reports:*").unwrap()]
+            );
+        }
+
+        #[test]
+        fn fail_other_template() {
+            let instance =
+                instantiate("docs:*", &[("tenant", "t-acme"), ("account", "42")]).unwrap();
+
+            let mut other = template("docs:*");
+            other.name = "TenantAdmin".to_string();
+
+            let actual = instance.reroll(&other).unwrap_err();
+
+            assert_eq!(
+                actual.kind,
+                TemplateErrorKind::TemplateMismatch {
+                    origin: "TenantReadOnly".to_string(),
+                }
+            );
+        }
+    }
+
+    mod is_glob_match {
+        use super::*;
+
+        #[test]
+        fn pass() {
+            for (pattern, value) in &[("t-*", "t-acme"), ("*", ""), ("a*b*c", "abc"), ("x", "x")] {
+                assert!(is_glob_match(pattern, value), "{} {}", pattern, value);
+            }
+        }
+
+        #[test]
+        fn fail() {
+            for (pattern, value) in &[("t-*", "acme"), ("a*a", "a"), ("a*b*c", "acb"), ("x", "y")] {
+                assert!(!is_glob_match(pattern, value), "{} {}", pattern, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::authorizer::{combine, is_applicable};
+use crate::{
+    CombiningAlgorithm, Decision, DenyOverrides, Effect, Policy, ScopedAction, ScopedResource,
+};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+/// The TenantPolicies describes the policies of many tenants hosted together, along with global
+/// policies that apply to every tenant.
+///
+/// The policies of each tenant are kept apart, so that evaluating a request of one tenant never
+/// considers the policies of another, whatever wildcards they contain. How the policies of a
+/// tenant combine with the global policies is set by the precedence of the tenant.
+#[derive(Debug, Default)]
+pub struct TenantPolicies {
+    /// The policies that apply to every tenant.
+    pub global: Vec<Policy>,
+
+    tenants: BTreeMap<String, Tenant>,
+}
+
+/// The TenantPrecedence describes how the policies of a tenant combine with the global policies.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TenantPrecedence {
+    /// The global and tenant policies are combined with deny-overrides, so that a tenant can grant
+    /// beyond the global policies or deny what they allow, but cannot lift a global deny.
+    #[default]
+    Combined,
+
+    /// The tenant policies override the global policies, which only decide the requests that no
+    /// tenant policy applies to.
+    TenantFirst,
+}
+
+/// The Tenant describes the policies of one tenant and how they combine with the global policies.
+#[derive(Debug, Default)]
+struct Tenant {
+    precedence: TenantPrecedence,
+    policies: Vec<Policy>,
+}
+
+/// The TenantDecision describes the effect of the global and tenant policies on a request.
+#[derive(Debug, PartialEq)]
+pub struct TenantDecision<'a> {
+    pub effect: Effect,
+
+    /// The global policies that determined the effect.
+    pub global: Vec<&'a Policy>,
+
+    /// The policies of the tenant that determined the effect.
+    pub tenant: Vec<&'a Policy>,
+}
+
+/// The UnknownTenant describes a request for a tenant that has not been added.
+#[derive(Debug, PartialEq)]
+pub struct UnknownTenant {
+    pub tenant: String,
+}
+
+impl TenantPolicies {
+    pub fn new(global: Vec<Policy>) -> Self {
+        Self {
+            global,
+            tenants: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the tenant, without policies of its own, if it has not been added.
+    pub fn add_tenant(&mut self, tenant: &str) {
+        self.tenants.entry(tenant.to_string()).or_default();
+    }
+
+    /// Adds the policy to the tenant, adding the tenant if it has not been added.
+    pub fn add_policy(&mut self, tenant: &str, policy: Policy) {
+        self.tenants
+            .entry(tenant.to_string())
+            .or_default()
+            .policies
+            .push(policy);
+    }
+
+    /// Sets how the policies of the tenant combine with the global policies, adding the tenant if
+    /// it has not been added.
+    pub fn set_precedence(&mut self, tenant: &str, precedence: TenantPrecedence) {
+        self.tenants
+            .entry(tenant.to_string())
+            .or_default()
+            .precedence = precedence;
+    }
+
+    /// Removes the tenant along with its policies, returning whether it had been added.
+    pub fn remove_tenant(&mut self, tenant: &str) -> bool {
+        self.tenants.remove(tenant).is_some()
+    }
+
+    /// Returns the policies of the tenant, or `None` if it has not been added.
+    pub fn policies(&self, tenant: &str) -> Option<&[Policy]> {
+        self.tenants
+            .get(tenant)
+            .map(|tenant| tenant.policies.as_slice())
+    }
+
+    /// Evaluates the global policies together with the policies of the tenant against the action
+    /// and resources, combining them as the precedence of the tenant sets.
+    pub fn evaluate(
+        &self,
+        tenant: &str,
+        scoped_action: &ScopedAction,
+        scoped_resources: &[ScopedResource],
+    ) -> Result<TenantDecision<'_>, UnknownTenant> {
+        let layer = self.tenants.get(tenant).ok_or_else(|| UnknownTenant {
+            tenant: tenant.to_string(),
+        })?;
+
+        let is_applicable =
+            |policy: &Policy| is_applicable(policy, scoped_action, scoped_resources);
+        let (global_decision, global) = combine(&DenyOverrides, &self.global, is_applicable);
+        let (tenant_decision, tenant) = combine(&DenyOverrides, &layer.policies, is_applicable);
+
+        let (decision, global, tenant) = match layer.precedence {
+            TenantPrecedence::Combined => {
+                let (decision, layers) = DenyOverrides.combine(&[global_decision, tenant_decision]);
+
+                (
+                    decision,
+                    if layers.contains(&0) { global } else { vec![] },
+                    if layers.contains(&1) { tenant } else { vec![] },
+                )
+            }
+            TenantPrecedence::TenantFirst if tenant_decision != Decision::NotApplicable => {
+                (tenant_decision, vec![], tenant)
+            }
+            TenantPrecedence::TenantFirst => (global_decision, global, vec![]),
+        };
+
+        Ok(TenantDecision {
+            effect: decision.effect(),
+            global,
+            tenant,
+        })
+    }
+}
+
+impl fmt::Display for UnknownTenant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown tenant {}", self.tenant)
+    }
+}
+
+impl Error for UnknownTenant {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::policy;
+
+    fn tenants() -> TenantPolicies {
+        let mut tenants = TenantPolicies::new(vec![
+            policy(Effect::Allow, "docs:read:*", "*"),
+            policy(Effect::Deny, "docs:purge:*", "*"),
+        ]);
+        tenants.add_tenant("acme");
+        tenants.add_policy("globex", policy(Effect::Allow, "*", "*"));
+        tenants
+    }
+
+    fn evaluate<'a>(
+        tenants: &'a TenantPolicies,
+        tenant: &str,
+        action: &str,
+    ) -> Result<TenantDecision<'a>, UnknownTenant> {
+        let action = ScopedAction::parse(action).unwrap();
+        let resources = vec![ScopedResource::parse("docs:readme").unwrap()];
+
+        tenants.evaluate(tenant, &action, &resources)
+    }
+
+    mod evaluate {
+        use super::*;
+
+        #[test]
+        fn pass_global() {
+            let tenants = tenants();
+
+            let actual = evaluate(&tenants, "acme", "docs:read:document").unwrap();
+
+            assert_eq!(
+                actual,
+                TenantDecision {
+                    effect: Effect::Allow,
+                    global: vec![&tenants.global[0]],
+                    tenant: vec![],
+                }
+            );
+        }
+
+        #[test]
+        fn pass_tenant() {
+            let tenants = tenants();
+
+            let actual = evaluate(&tenants, "globex", "docs:write:document").unwrap();
+
+            assert_eq!(
+                actual,
+                TenantDecision {
+                    effect: Effect::Allow,
+                    global: vec![],
+                    tenant: vec![&tenants.policies("globex").unwrap()[0]],
+                }
+            );
+        }
+
+        #[test]
+        fn fail_other_tenant() {
+            let tenants = tenants();
+
+            let actual = evaluate(&tenants, "acme", "docs:write:document").unwrap();
+
+            assert_eq!(actual.effect, Effect::Deny);
+        }
+
+        #[test]
+        fn fail_tenant_deny() {
+            let mut tenants = tenants();
+            tenants.add_policy("acme", policy(Effect::Deny, "docs:read:*", "docs:secret"));
+
+            let actual = evaluate(&tenants, "acme", "docs:read:document").unwrap();
+            let other = tenants
+                .evaluate(
+                    "acme",
+                    &ScopedAction::parse("docs:read:document").unwrap(),
+                    &[ScopedResource::parse("docs:secret").unwrap()],
+                )
+                .unwrap();
+
+            assert_eq!(actual.effect, Effect::Allow);
+            assert_eq!(other.effect, Effect::Deny);
+        }
+
+        #[test]
+        fn fail_global_deny() {
+            let tenants = tenants();
+
+            let actual = evaluate(&tenants, "globex", "docs:purge:document").unwrap();
+
+            assert_eq!(
+                actual,
+                TenantDecision {
+                    effect: Effect::Deny,
+                    global: vec![&tenants.global[1]],
+                    tenant: vec![],
+                }
+            );
+        }
+
+        #[test]
+        fn pass_tenant_first() {
+            let mut tenants = tenants();
+            tenants.set_precedence("globex", TenantPrecedence::TenantFirst);
+
+            let actual = evaluate(&tenants, "globex", "docs:purge:document").unwrap();
+
+            assert_eq!(
+                actual,
+                TenantDecision {
+                    effect: Effect::Allow,
+                    global: vec![],
+                    tenant: vec![&tenants.policies("globex").unwrap()[0]],
+                }
+            );
+        }
+
+        #[test]
+        fn fail_tenant_first_global() {
+            let mut tenants = tenants();
+            tenants.set_precedence("acme", TenantPrecedence::TenantFirst);
+
+            let actual = evaluate(&tenants, "acme", "docs:purge:document").unwrap();
+
+            assert_eq!(
+                actual,
+                TenantDecision {
+                    effect: Effect::Deny,
+                    global: vec![&tenants.global[1]],
+                    tenant: vec![],
+                }
+            );
+        }
+
+        #[test]
+        fn fail_unknown() {
+            let mut tenants = tenants();
+            tenants.remove_tenant("acme");
+
+            let actual = evaluate(&tenants, "acme", "docs:read:document").unwrap_err();
+
+            assert_eq!(
+                actual,
+                UnknownTenant {
+                    tenant: "acme".to_string(),
+                }
+            );
+        }
+    }
+}
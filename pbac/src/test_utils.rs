@@ -0,0 +1,28 @@
+use crate::{ActionDocument, Effect, Element, Policy, Principal, ResourceDocument};
+
+/// Returns a policy of the action on the resource, applying to every principal.
+pub(crate) fn policy(effect: Effect, action: &str, resource: &str) -> Policy {
+    Policy {
+        actions: vec![ActionDocument::parse(action).unwrap()],
+        effect,
+        principals: vec![],
+        resources: vec![ResourceDocument::parse(resource).unwrap()],
+    }
+}
+
+/// Returns a policy of the action on the resource, applying to the principals.
+pub(crate) fn principal_policy(
+    effect: Effect,
+    principals: &[&str],
+    action: &str,
+    resource: &str,
+) -> Policy {
+    Policy {
+        principals: principals.iter().map(|value| principal(value)).collect(),
+        ..policy(effect, action, resource)
+    }
+}
+
+pub(crate) fn principal(value: &str) -> Principal {
+    Principal(value.to_string())
+}
@@ -1,7 +1,12 @@
+// The baseline tests borrow their resources as written.
+#![allow(clippy::needless_borrow, clippy::useless_vec)]
+
 use pbac::{
-    is_authorized, ActionDocument, Effect, Element, Policy, ResourceDocument, ScopedAction,
-    ScopedResource,
+    check_resource_types, evaluate, is_authorized, ActionDocument, CombiningAlgorithm, Decision,
+    DenyOverrides, DenyUnlessPermit, Effect, Element, FirstApplicable, OnlyOneApplicable,
+    PermitOverrides, Policy, ResourceDocument, ResourceTypeMismatch, ScopedAction, ScopedResource,
 };
+use Decision::{Allow as A, Deny as D, Indeterminate as I, NotApplicable as N};
 
 #[test]
 fn implicit_deny_when_no_policies() {
@@ -123,3 +128,296 @@ fn implicit_deny_if_resource_not_match() {
     assert_eq!(effect, Effect::Deny);
     assert_eq!(policies, Vec::<&Policy>::new());
 }
+
+#[test]
+fn resource_types_match() {
+    let action = ScopedAction::parse("docs:read:document").unwrap();
+    let resources = vec![
+        ScopedResource::parse("docs:readme")
+            .unwrap()
+            .with_type("document"),
+        ScopedResource::parse("docs:notes").unwrap(),
+    ];
+
+    let mismatches = check_resource_types(&action, &resources);
+
+    assert_eq!(mismatches, vec![]);
+}
+
+#[test]
+fn resource_types_mismatch_scope() {
+    let action = ScopedAction::parse("docs:read:document").unwrap();
+    let resources = vec![ScopedResource::parse("billing:invoice-1").unwrap()];
+
+    let mismatches = check_resource_types(&action, &resources);
+
+    assert_eq!(
+        mismatches,
+        vec![ResourceTypeMismatch {
+            action: "docs:read:document".to_string(),
+            resource: "billing:invoice-1".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn resource_types_mismatch_type() {
+    let action = ScopedAction::parse("docs:read:document").unwrap();
+    let resources = vec![
+        ScopedResource::parse("docs:readme")
+            .unwrap()
+            .with_type("document"),
+        ScopedResource::parse("docs:home")
+            .unwrap()
+            .with_type("folder"),
+    ];
+
+    let mismatches = check_resource_types(&action, &resources);
+
+    assert_eq!(
+        mismatches,
+        vec![ResourceTypeMismatch {
+            action: "docs:read:document".to_string(),
+            resource: "docs:home".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn resource_types_mismatch_denied() {
+    let policies = vec![Policy {
+        actions: vec![ActionDocument::parse("*").unwrap()],
+        effect: Effect::Allow,
+        principals: vec![],
+        resources: vec![ResourceDocument::parse("*").unwrap()],
+    }];
+
+    let action = ScopedAction::parse("docs:read:document").unwrap();
+    let resources = vec![ScopedResource::parse("docs:home")
+        .unwrap()
+        .with_type("folder")];
+
+    let (effect, policies) = is_authorized(&policies, &action, &resources);
+
+    assert_eq!(effect, Effect::Deny);
+    assert_eq!(policies, Vec::<&Policy>::new());
+}
+
+#[test]
+fn resource_types_untyped_unchecked() {
+    let policies = vec![Policy {
+        actions: vec![ActionDocument::parse("*").unwrap()],
+        effect: Effect::Allow,
+        principals: vec![],
+        resources: vec![ResourceDocument::parse("*").unwrap()],
+    }];
+
+    let action = ScopedAction::parse("docs:read:document").unwrap();
+    let resources = vec![ScopedResource::parse("files:bucket/key").unwrap()];
+
+    let (effect, _) = is_authorized(&policies, &action, &resources);
+
+    assert_eq!(effect, Effect::Allow);
+}
+
+#[test]
+fn evaluate_indeterminate_when_resource_types_mismatch() {
+    let policies = vec![Policy {
+        actions: vec![ActionDocument::parse("*").unwrap()],
+        effect: Effect::Allow,
+        principals: vec![],
+        resources: vec![ResourceDocument::parse("*").unwrap()],
+    }];
+
+    let action = ScopedAction::parse("docs:read:document").unwrap();
+    let resources = vec![ScopedResource::parse("billing:invoice-1")
+        .unwrap()
+        .with_type("invoice")];
+
+    let (decision, policies) = evaluate(&PermitOverrides, &policies, &action, &resources);
+
+    assert_eq!(decision, Decision::Indeterminate);
+    assert_eq!(policies, Vec::<&Policy>::new());
+}
+
+/// Combines each of the decisions with the algorithm, comparing against the expected outcome.
+fn assert_combines(
+    algorithm: &dyn CombiningAlgorithm,
+    cases: &[(&[Decision], Decision, &[usize])],
+) {
+    for (decisions, decision, indices) in cases {
+        let actual = algorithm.combine(decisions);
+
+        assert_eq!(
+            actual,
+            (*decision, indices.to_vec()),
+            "{:?} combining {:?}",
+            algorithm,
+            decisions
+        );
+    }
+}
+
+#[test]
+fn combining_deny_overrides() {
+    assert_combines(
+        &DenyOverrides,
+        &[
+            (&[], N, &[]),
+            (&[N, N], N, &[]),
+            (&[A], A, &[0]),
+            (&[D], D, &[0]),
+            (&[I], I, &[0]),
+            (&[A, N, A], A, &[0, 2]),
+            (&[A, D], D, &[1]),
+            (&[D, A, D], D, &[0, 2]),
+            (&[A, I], I, &[1]),
+            (&[I, D], D, &[1]),
+        ],
+    );
+}
+
+#[test]
+fn combining_permit_overrides() {
+    assert_combines(
+        &PermitOverrides,
+        &[
+            (&[], N, &[]),
+            (&[N, N], N, &[]),
+            (&[A], A, &[0]),
+            (&[D], D, &[0]),
+            (&[I], I, &[0]),
+            (&[D, N, D], D, &[0, 2]),
+            (&[D, A], A, &[1]),
+            (&[A, D, A], A, &[0, 2]),
+            (&[D, I], I, &[1]),
+            (&[I, A], A, &[1]),
+        ],
+    );
+}
+
+#[test]
+fn combining_first_applicable() {
+    assert_combines(
+        &FirstApplicable,
+        &[
+            (&[], N, &[]),
+            (&[N, N], N, &[]),
+            (&[A], A, &[0]),
+            (&[D], D, &[0]),
+            (&[I], I, &[0]),
+            (&[N, A, D], A, &[1]),
+            (&[N, D, A], D, &[1]),
+            (&[I, A], I, &[0]),
+        ],
+    );
+}
+
+#[test]
+fn combining_only_one_applicable() {
+    assert_combines(
+        &OnlyOneApplicable,
+        &[
+            (&[], N, &[]),
+            (&[N, N], N, &[]),
+            (&[A], A, &[0]),
+            (&[D], D, &[0]),
+            (&[I], I, &[0]),
+            (&[N, A, N], A, &[1]),
+            (&[N, D, N], D, &[1]),
+            (&[A, N, A], I, &[0, 2]),
+            (&[A, D], I, &[0, 1]),
+        ],
+    );
+}
+
+#[test]
+fn combining_deny_unless_permit() {
+    assert_combines(
+        &DenyUnlessPermit,
+        &[
+            (&[], D, &[]),
+            (&[N, N], D, &[]),
+            (&[A], A, &[0]),
+            (&[D], D, &[0]),
+            (&[I], D, &[]),
+            (&[D, A], A, &[1]),
+            (&[N, D, I], D, &[1]),
+        ],
+    );
+}
+
+#[test]
+fn combining_decisions_are_never_not_applicable_under_deny_unless_permit() {
+    let values = [A, D, N, I];
+
+    for &a in &values {
+        for &b in &values {
+            for &c in &values {
+                let (decision, _) = DenyUnlessPermit.combine(&[a, b, c]);
+
+                assert_ne!(decision, N);
+            }
+        }
+    }
+}
+
+#[test]
+fn evaluate_combines_applicable_policies() {
+    let policies = vec![
+        Policy {
+            actions: vec![ActionDocument::parse("scope:*").unwrap()],
+            effect: Effect::Allow,
+            principals: vec![],
+            resources: vec![ResourceDocument::parse("*").unwrap()],
+        },
+        Policy {
+            actions: vec![ActionDocument::parse("scope:delete:*").unwrap()],
+            effect: Effect::Deny,
+            principals: vec![],
+            resources: vec![ResourceDocument::parse("*").unwrap()],
+        },
+        Policy {
+            actions: vec![ActionDocument::parse("other:*").unwrap()],
+            effect: Effect::Deny,
+            principals: vec![],
+            resources: vec![ResourceDocument::parse("*").unwrap()],
+        },
+    ];
+
+    let action = ScopedAction::parse("scope:delete:resource").unwrap();
+    let resources = vec![ScopedResource::parse("scope:resource").unwrap()];
+
+    let cases: &[(&dyn CombiningAlgorithm, Decision, Vec<&Policy>)] = &[
+        (&DenyOverrides, D, vec![&policies[1]]),
+        (&PermitOverrides, A, vec![&policies[0]]),
+        (&FirstApplicable, A, vec![&policies[0]]),
+        (&OnlyOneApplicable, I, vec![&policies[0], &policies[1]]),
+        (&DenyUnlessPermit, A, vec![&policies[0]]),
+    ];
+
+    for (algorithm, decision, matched) in cases {
+        let actual = evaluate(*algorithm, &policies, &action, &resources);
+
+        assert_eq!(actual, (*decision, matched.clone()), "{:?}", algorithm);
+    }
+}
+
+#[test]
+fn evaluate_not_applicable_when_no_policy_applies() {
+    let policies = vec![Policy {
+        actions: vec![ActionDocument::parse("other:*").unwrap()],
+        effect: Effect::Allow,
+        principals: vec![],
+        resources: vec![ResourceDocument::parse("*").unwrap()],
+    }];
+
+    let action = ScopedAction::parse("scope:verb:resource").unwrap();
+    let resources = vec![ScopedResource::parse("scope:resource").unwrap()];
+
+    let (decision, policies) = evaluate(&FirstApplicable, &policies, &action, &resources);
+
+    assert_eq!(decision, N);
+    assert_eq!(decision.effect(), Effect::Deny);
+    assert_eq!(policies, Vec::<&Policy>::new());
+}